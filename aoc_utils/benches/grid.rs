@@ -0,0 +1,133 @@
+//! Compares the flat `Grid` storage against the nested `Vec<Vec<T>>` layout
+//! which it replaced. Run with `cargo bench -p aoc_utils`.
+//!
+//! Under the workspace's size-optimised release profile the two layouts are
+//! level for random lookups, cloning and full scans, while the nested layout
+//! is still ahead on neighbour lookups. At `opt-level = 3`
+//! (`CARGO_PROFILE_BENCH_OPT_LEVEL=3`) the flat layout is level or ahead on
+//! neighbour lookups too, so the gap comes from what the size profile chooses
+//! not to inline rather than from the layout.
+//!
+//! The flat layout is kept for what it makes possible rather than for lookup
+//! speed: the whole grid is one slice for `as_slice`, `from_vec`, rendering
+//! and `BitGrid::from_grid`; positions and indices convert both ways with
+//! `index_of` and `position_of`; and every row is guaranteed to be the same
+//! width.
+
+use aoc_utils::grid::Grid;
+use aoc_utils::position::Position;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const SIZE: usize = 4096;
+const STEPS: usize = 5_000_000;
+const RUNS: u32 = 5;
+
+fn time<F: FnMut() -> u64>(mut run: F) -> Duration {
+    let mut best = Duration::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        black_box(run());
+        best = best.min(start.elapsed());
+    }
+    best
+}
+
+/// Hops around the grid where each hop depends on the value just read, so
+/// every lookup has to wait on the previous one, much like a beam or a guard
+/// patrol does.
+fn walk<G: Fn(&Position) -> u64>(get: G) -> u64 {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut total = 0;
+    for _ in 0..STEPS {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let position = Position {
+            row: (state >> 32) as usize % SIZE,
+            column: (state as usize ^ total as usize) % SIZE,
+        };
+        total += get(&position);
+    }
+    total
+}
+
+/// Sums every cell with its orthogonal neighbours.
+fn neighbour_sum<G: Fn(&Position) -> Option<u64>>(get: G) -> u64 {
+    let mut total = 0;
+    for row in 0..SIZE {
+        for column in 0..SIZE {
+            let position = Position { row, column };
+            total += get(&position).unwrap_or(0);
+            total += get(&Position {
+                row: row + 1,
+                ..position
+            })
+            .unwrap_or(0);
+            total += get(&Position {
+                column: column + 1,
+                ..position
+            })
+            .unwrap_or(0);
+            if row > 0 {
+                total += get(&Position {
+                    row: row - 1,
+                    ..position
+                })
+                .unwrap_or(0);
+            }
+            if column > 0 {
+                total += get(&Position {
+                    column: column - 1,
+                    ..position
+                })
+                .unwrap_or(0);
+            }
+        }
+    }
+    total
+}
+
+fn main() {
+    let nested: Vec<Vec<u32>> = (0..SIZE)
+        .map(|row| {
+            (0..SIZE)
+                .map(|column| ((row * 31 + column * 17) % 97) as u32)
+                .collect()
+        })
+        .collect();
+    let grid = Grid::new(nested.clone());
+
+    let nested_get = |position: &Position| -> Option<u64> {
+        nested
+            .get(position.row)?
+            .get(position.column)
+            .map(|&value| value as u64)
+    };
+    let grid_get =
+        |position: &Position| -> Option<u64> { grid.get(position).map(|&value| value as u64) };
+
+    let nested_walk = time(|| walk(|position| nested_get(position).unwrap()));
+    let grid_walk = time(|| walk(|position| grid[position] as u64));
+    println!("random walk ({STEPS} steps on {SIZE}x{SIZE})");
+    println!("  Vec<Vec<T>>: {nested_walk:?}");
+    println!("  Grid<T>:     {grid_walk:?}");
+
+    let nested_neighbours = time(|| neighbour_sum(nested_get));
+    let grid_neighbours = time(|| neighbour_sum(grid_get));
+    println!("neighbour sum ({SIZE}x{SIZE})");
+    println!("  Vec<Vec<T>>: {nested_neighbours:?}");
+    println!("  Grid<T>:     {grid_neighbours:?}");
+
+    let nested_clone = time(|| black_box(nested.clone()).len() as u64);
+    let grid_clone = time(|| black_box(grid.clone()).as_slice().len() as u64);
+    println!("clone ({SIZE}x{SIZE})");
+    println!("  Vec<Vec<T>>: {nested_clone:?}");
+    println!("  Grid<T>:     {grid_clone:?}");
+
+    let nested_scan = time(|| nested.iter().flatten().map(|&value| value as u64).sum());
+    let grid_scan = time(|| grid.as_slice().iter().map(|&value| value as u64).sum());
+    println!("full scan ({SIZE}x{SIZE})");
+    println!("  Vec<Vec<T>>: {nested_scan:?}");
+    println!("  Grid<T>:     {grid_scan:?}");
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A sequence of states which eventually repeats. The states before `start`
+/// are only seen once, after which the sequence loops every `period` steps.
+#[derive(Clone, Debug)]
+pub struct Cycle<S> {
+    pub start: usize,
+    pub period: usize,
+    history: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// The state after `n` steps from the initial state.
+    pub fn state_at(&self, n: usize) -> &S {
+        let index = if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        };
+        &self.history[index]
+    }
+}
+
+/// Repeatedly applies `step` to the initial state until a state repeats,
+/// remembering every state seen along the way. This will not terminate if the
+/// sequence never repeats.
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> Cycle<S>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut history = vec![];
+    let mut state = initial;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            return Cycle {
+                start,
+                period: history.len() - start,
+                history,
+            };
+        }
+        seen.insert(state.clone(), history.len());
+        let next = step(&state);
+        history.push(state);
+        state = next;
+    }
+}
+
+/// Finds the `(start, period)` of a repeating sequence using Brent's
+/// algorithm, which only ever holds a couple of states in memory at a time.
+pub fn brent<S, F>(initial: S, mut step: F) -> (usize, usize)
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // Find the period by racing ahead in ever larger powers of two
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // Then find the start by walking two states a period apart in step
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    (start, period)
+}
+
+/// The state after `n` steps, skipping over whole cycles rather than
+/// simulating every step.
+pub fn state_at<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let (start, period) = brent(initial.clone(), &mut step);
+    let remaining = if n < start {
+        n
+    } else {
+        start + (n - start) % period
+    };
+
+    let mut state = initial;
+    for _ in 0..remaining {
+        state = step(&state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    fn step(x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    fn simulate(n: usize) -> u32 {
+        (0..n).fold(3, |x, _| step(&x))
+    }
+
+    #[test]
+    fn it_finds_a_cycle() {
+        let cycle = find_cycle(3, step);
+        assert_eq!(cycle.start, 2);
+        assert_eq!(cycle.period, 6);
+
+        for n in 0..50 {
+            assert_eq!(*cycle.state_at(n), simulate(n));
+        }
+    }
+
+    #[test]
+    fn it_agrees_with_brent() {
+        let cycle = find_cycle(3, step);
+        assert_eq!(brent(3, step), (cycle.start, cycle.period));
+    }
+
+    #[test]
+    fn it_skips_to_a_distant_state() {
+        assert_eq!(
+            state_at(3, step, 1_000_000_000),
+            simulate(2 + (1_000_000_000 - 2) % 6)
+        );
+        assert_eq!(state_at(3, step, 1), simulate(1));
+    }
+
+    #[test]
+    fn it_finds_a_cycle_of_grids() {
+        let grid = Grid::new(vec![vec![1, 2], vec![3, 4]]);
+        let cycle = find_cycle(grid.clone(), |grid| grid.rotate_clockwise().to_grid());
+
+        assert_eq!(cycle.start, 0);
+        assert_eq!(cycle.period, 4);
+        assert_eq!(
+            cycle.state_at(1_000_000_001),
+            &grid.rotate_clockwise().to_grid()
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::position::Position;
+use crate::rect::Rect;
 use crate::traits::{Integer, One, Zero};
 use std::cmp::PartialOrd;
 use std::ops::{Add, Rem, Sub};
@@ -12,6 +13,31 @@ pub enum Direction {
 }
 
 impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_clockwise(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_anticlockwise(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
     pub fn travel<T>(&self, position: &Position<T>) -> Position<T>
     where
         T: Copy + Sub<Output = T> + Add<Output = T> + Integer + One,
@@ -36,93 +62,83 @@ impl Direction {
         }
     }
 
-    pub fn travel_with_bounds<T>(
+    /// Moves one step, or `None` if that would leave the boundary. The
+    /// boundary can be a `Rect`, or a `Position` which is treated as the
+    /// rectangle from zero up to it.
+    pub fn travel_with_bounds<T, B>(
         &self,
         position: &Position<T>,
-        boundary: &Position<T>,
+        boundary: &B,
     ) -> Option<Position<T>>
     where
         T: Copy + Sub<Output = T> + Add<Output = T> + PartialOrd + Integer + One + Zero,
+        B: Copy + Into<Rect<T>>,
     {
-        match self {
-            Direction::Up => {
-                if position.row > T::zero() {
-                    Some(Position {
-                        row: position.row - T::one(),
-                        column: position.column,
-                    })
-                } else {
-                    None
-                }
-            }
-            Direction::Down => {
-                if position.row + T::one() < boundary.row {
-                    Some(Position {
-                        row: position.row + T::one(),
-                        column: position.column,
-                    })
-                } else {
-                    None
-                }
-            }
-            Direction::Left => {
-                if position.column > T::zero() {
-                    Some(Position {
-                        row: position.row,
-                        column: position.column - T::one(),
-                    })
-                } else {
-                    None
-                }
-            }
-            Direction::Right => {
-                if position.column + T::one() < boundary.column {
-                    Some(Position {
-                        row: position.row,
-                        column: position.column + T::one(),
-                    })
-                } else {
-                    None
-                }
-            }
-        }
+        Heading::from(self).travel_with_bounds(position, boundary)
     }
 
     pub fn travel_with_wrap<T>(&self, position: &Position<T>, boundary: &Position<T>) -> Position<T>
     where
-        T: Copy + Sub<Output = T> + Add<Output = T> + Rem<Output = T> + Integer + One + PartialOrd,
+        T: Copy
+            + Sub<Output = T>
+            + Add<Output = T>
+            + Rem<Output = T>
+            + Integer
+            + One
+            + Zero
+            + PartialOrd,
     {
-        let unwrapped_position = self.travel(position);
-        Position {
-            row: ((unwrapped_position.row) % boundary.row + boundary.row) % boundary.row,
-            column: ((unwrapped_position.column) % boundary.column + boundary.column)
-                % boundary.column,
-        }
+        // Bring the position into the boundary first, then step without ever
+        // going below zero so that unsigned types cannot underflow.
+        let position = Position {
+            row: (position.row % boundary.row + boundary.row) % boundary.row,
+            column: (position.column % boundary.column + boundary.column) % boundary.column,
+        };
+        self.travel_with_bounds(&position, boundary)
+            .unwrap_or_else(|| match self {
+                Direction::Up => Position {
+                    row: boundary.row - T::one(),
+                    column: position.column,
+                },
+                Direction::Down => Position {
+                    row: T::zero(),
+                    column: position.column,
+                },
+                Direction::Left => Position {
+                    row: position.row,
+                    column: boundary.column - T::one(),
+                },
+                Direction::Right => Position {
+                    row: position.row,
+                    column: T::zero(),
+                },
+            })
     }
 
     pub fn travel_n<T>(&self, position: &Position<T>, n: usize) -> Position<T>
     where
         T: Copy + Sub<Output = T> + Add<Output = T> + Integer + One,
     {
-        let mut new_position = position.clone();
+        let mut new_position = *position;
         for _ in 0..n {
             new_position = self.travel(&new_position);
         }
         new_position
     }
 
-    pub fn travel_n_with_bounds<T>(
+    pub fn travel_n_with_bounds<T, B>(
         &self,
         position: &Position<T>,
-        boundary: &Position<T>,
+        boundary: &B,
         n: usize,
     ) -> Option<Position<T>>
     where
         T: Copy + Sub<Output = T> + Add<Output = T> + PartialOrd + Integer + One + Zero,
+        B: Copy + Into<Rect<T>>,
     {
-        let mut new_position = position.clone();
+        let mut new_position = *position;
         for _ in 0..n {
-            new_position = self.travel_with_bounds(&new_position, &boundary)?;
+            new_position = self.travel_with_bounds(&new_position, boundary)?;
         }
         Some(new_position)
     }
@@ -134,9 +150,16 @@ impl Direction {
         n: usize,
     ) -> Position<T>
     where
-        T: Copy + Sub<Output = T> + Add<Output = T> + Rem<Output = T> + Integer + One + PartialOrd,
+        T: Copy
+            + Sub<Output = T>
+            + Add<Output = T>
+            + Rem<Output = T>
+            + Integer
+            + One
+            + Zero
+            + PartialOrd,
     {
-        let mut new_position = position.clone();
+        let mut new_position = *position;
         for _ in 0..n {
             new_position = self.travel_with_wrap(&new_position, &boundary);
         }
@@ -144,6 +167,123 @@ impl Direction {
     }
 }
 
+/// One of the eight directions between neighbouring cells. `Direction` covers
+/// the four orthogonal ones and converts into this.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum Heading {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Heading {
+    pub const ALL: [Heading; 8] = [
+        Heading::Up,
+        Heading::UpRight,
+        Heading::Right,
+        Heading::DownRight,
+        Heading::Down,
+        Heading::DownLeft,
+        Heading::Left,
+        Heading::UpLeft,
+    ];
+
+    pub const ORTHOGONAL: [Heading; 4] =
+        [Heading::Up, Heading::Right, Heading::Down, Heading::Left];
+
+    pub const DIAGONAL: [Heading; 4] = [
+        Heading::UpRight,
+        Heading::DownRight,
+        Heading::DownLeft,
+        Heading::UpLeft,
+    ];
+
+    /// The change in row and column from taking one step in this heading.
+    pub fn offset(&self) -> Position<isize> {
+        let (row, column) = match self {
+            Heading::Up => (-1, 0),
+            Heading::UpRight => (-1, 1),
+            Heading::Right => (0, 1),
+            Heading::DownRight => (1, 1),
+            Heading::Down => (1, 0),
+            Heading::DownLeft => (1, -1),
+            Heading::Left => (0, -1),
+            Heading::UpLeft => (-1, -1),
+        };
+        Position { row, column }
+    }
+
+    /// Moves one step, or `None` if that would leave the boundary. The
+    /// boundary can be a `Rect`, or a `Position` which is treated as the
+    /// rectangle from zero up to it.
+    pub fn travel_with_bounds<T, B>(
+        &self,
+        position: &Position<T>,
+        boundary: &B,
+    ) -> Option<Position<T>>
+    where
+        T: Copy + Sub<Output = T> + Add<Output = T> + PartialOrd + One,
+        B: Copy + Into<Rect<T>>,
+    {
+        let boundary: Rect<T> = (*boundary).into();
+        // Refuse to step below the boundary before subtracting, so that
+        // unsigned positions cannot underflow
+        let step = |value: T, offset: isize, min: T| match offset {
+            -1 => (value > min).then(|| value - T::one()),
+            1 => Some(value + T::one()),
+            _ => Some(value),
+        };
+        let offset = self.offset();
+        let next = Position {
+            row: step(position.row, offset.row, boundary.min.row)?,
+            column: step(position.column, offset.column, boundary.min.column)?,
+        };
+        boundary.contains(&next).then_some(next)
+    }
+
+    /// Moves `n` steps from a position, or `None` if that would leave the
+    /// boundary.
+    pub fn travel_n_with_bounds<B>(
+        &self,
+        position: &Position,
+        boundary: &B,
+        n: usize,
+    ) -> Option<Position>
+    where
+        B: Copy + Into<Rect>,
+    {
+        let n = isize::try_from(n).ok()?;
+        let offset = self.offset();
+        let offset = Position {
+            row: offset.row.checked_mul(n)?,
+            column: offset.column.checked_mul(n)?,
+        };
+        position.checked_offset(&offset, boundary)
+    }
+}
+
+impl From<&Direction> for Heading {
+    fn from(direction: &Direction) -> Self {
+        match direction {
+            Direction::Up => Heading::Up,
+            Direction::Right => Heading::Right,
+            Direction::Down => Heading::Down,
+            Direction::Left => Heading::Left,
+        }
+    }
+}
+
+impl From<Direction> for Heading {
+    fn from(direction: Direction) -> Self {
+        Heading::from(&direction)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,6 +320,14 @@ mod tests {
         assert_eq!(new_position, expected_position);
     }
 
+    #[test]
+    fn it_turns() {
+        assert_eq!(Direction::Up.turn_clockwise(), Direction::Right);
+        assert_eq!(Direction::Left.turn_clockwise(), Direction::Up);
+        assert_eq!(Direction::Up.turn_anticlockwise(), Direction::Left);
+        assert_eq!(Direction::Right.turn_anticlockwise(), Direction::Up);
+    }
+
     #[test]
     fn it_stays_in_bounds() {
         let position = Position { row: 0, column: 9 };
@@ -198,6 +346,55 @@ mod tests {
         assert!(in_bounds_case.is_some());
     }
 
+    #[test]
+    fn it_stays_in_a_rectangle() {
+        let position = Position { row: -2, column: 3 };
+        let bounds = Rect::new(
+            Position {
+                row: -2,
+                column: -5,
+            },
+            Position { row: 4, column: 4 },
+        );
+
+        assert_eq!(Direction::Up.travel_with_bounds(&position, &bounds), None);
+        assert_eq!(
+            Direction::Left.travel_n_with_bounds(&position, &bounds, 8),
+            Some(Position {
+                row: -2,
+                column: -5
+            })
+        );
+        assert_eq!(
+            Direction::Left.travel_n_with_bounds(&position, &bounds, 9),
+            None
+        );
+        assert_eq!(
+            Direction::Right.travel_with_bounds(&position, &bounds),
+            None
+        );
+    }
+
+    #[test]
+    fn it_travels_along_headings() {
+        let position = Position { row: 1, column: 2 };
+        let boundary = Position { row: 5, column: 5 };
+
+        assert_eq!(
+            Heading::DownRight.travel_n_with_bounds(&position, &boundary, 2),
+            Some(Position { row: 3, column: 4 })
+        );
+        assert_eq!(
+            Heading::DownRight.travel_n_with_bounds(&position, &boundary, 3),
+            None
+        );
+        assert_eq!(
+            Heading::UpLeft.travel_n_with_bounds(&position, &boundary, 2),
+            None
+        );
+        assert_eq!(Heading::from(Direction::Left), Heading::Left);
+    }
+
     #[test]
     fn it_wraps_around() {
         let position = Position { row: 0, column: 9 };
@@ -212,4 +409,28 @@ mod tests {
         let over_case = Direction::Right.travel_with_wrap(&position, &boundary);
         assert_eq!(over_case, Position { row: 0, column: 0 });
     }
+
+    #[test]
+    fn it_wraps_around_unsigned_positions() {
+        let position: Position<usize> = Position { row: 0, column: 0 };
+        let boundary = Position { row: 3, column: 4 };
+
+        let up = Direction::Up.travel_with_wrap(&position, &boundary);
+        assert_eq!(up, Position { row: 2, column: 0 });
+
+        let left = Direction::Left.travel_n_with_wrap(&position, &boundary, 5);
+        assert_eq!(left, Position { row: 0, column: 3 });
+    }
+
+    #[test]
+    fn it_wraps_around_signed_positions_outside_the_boundary() {
+        let position: Position<i32> = Position {
+            row: -1,
+            column: 13,
+        };
+        let boundary = Position { row: 3, column: 4 };
+
+        let down = Direction::Down.travel_with_wrap(&position, &boundary);
+        assert_eq!(down, Position { row: 0, column: 1 });
+    }
 }
@@ -0,0 +1,117 @@
+use crate::grid::Grid;
+use crate::position::Position;
+use std::collections::VecDeque;
+
+impl<T> Grid<T> {
+    /// Finds the number of orthogonal steps from the nearest source to every
+    /// cell, only passing through cells which satisfy `passable`. Sources are
+    /// always at distance zero, and unreachable cells are `None`.
+    pub fn bfs_distances<I, P>(&self, sources: I, mut passable: P) -> Grid<Option<u32>>
+    where
+        I: IntoIterator<Item = Position>,
+        P: FnMut(&Position, &T) -> bool,
+    {
+        let mut distances = Grid::from_vec(vec![None; self.data.len()], self.width);
+        let mut queue = VecDeque::new();
+
+        for source in sources {
+            if let Some(distance) = distances.get_mut(&source)
+                && distance.is_none()
+            {
+                *distance = Some(0);
+                queue.push_back((source, 0));
+            }
+        }
+
+        while let Some((position, distance)) = queue.pop_front() {
+            for (neighbour, value) in self.neighbours(&position) {
+                if distances[&neighbour].is_none() && passable(&neighbour, value) {
+                    distances.set(&neighbour, Some(distance + 1));
+                    queue.push_back((neighbour, distance + 1));
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+impl Grid<Option<u32>> {
+    /// Reconstructs a shortest path from a source to the target by stepping
+    /// back through ever smaller distances. The path starts at the source and
+    /// ends at the target, or is `None` if the target was never reached.
+    pub fn path_to(&self, target: &Position) -> Option<Vec<Position>> {
+        let mut distance = (*self.get(target)?)?;
+        let mut path = vec![*target];
+
+        while distance > 0 {
+            let current = path.last().unwrap();
+            let (previous, _) = self
+                .neighbours(current)
+                .find(|(_, value)| **value == Some(distance - 1))?;
+            path.push(previous);
+            distance -= 1;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maze() -> Grid<char> {
+        Grid::from_str(
+            "\
+S.#.
+.##.
+...E",
+            |c| c,
+        )
+    }
+
+    #[test]
+    fn it_finds_distances() {
+        let grid = maze();
+        let start = Position { row: 0, column: 0 };
+        let distances = grid.bfs_distances([start], |_, &c| c != '#');
+
+        assert_eq!(distances[Position { row: 0, column: 0 }], Some(0));
+        assert_eq!(distances[Position { row: 2, column: 3 }], Some(5));
+        assert_eq!(distances[Position { row: 0, column: 3 }], Some(7));
+        assert_eq!(distances[Position { row: 0, column: 2 }], None);
+    }
+
+    #[test]
+    fn it_finds_distances_from_multiple_sources() {
+        let grid = maze();
+        let sources = [
+            Position { row: 0, column: 0 },
+            Position { row: 0, column: 3 },
+        ];
+        let distances = grid.bfs_distances(sources, |_, &c| c != '#');
+
+        assert_eq!(distances[Position { row: 2, column: 3 }], Some(2));
+        assert_eq!(distances[Position { row: 2, column: 1 }], Some(3));
+    }
+
+    #[test]
+    fn it_reconstructs_a_path() {
+        let grid = maze();
+        let start = Position { row: 0, column: 0 };
+        let distances = grid.bfs_distances([start], |_, &c| c != '#');
+
+        let path = distances.path_to(&Position { row: 2, column: 3 }).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!(path.first(), Some(&Position { row: 0, column: 0 }));
+        assert_eq!(path.last(), Some(&Position { row: 2, column: 3 }));
+        assert!(
+            path.windows(2)
+                .all(|pair| Position::manhattan_distance(&pair[0], &pair[1]) == 1)
+        );
+
+        assert_eq!(distances.path_to(&Position { row: 0, column: 2 }), None);
+    }
+}
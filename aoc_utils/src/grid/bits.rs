@@ -0,0 +1,284 @@
+use crate::grid::Grid;
+use crate::position::Position;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A rectangular grid of booleans, packed into one bit per cell. This is much
+/// smaller and faster than a `HashSet<Position>` for keeping track of which
+/// cells have been visited.
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    width: usize,
+    height: usize,
+}
+
+impl BitGrid {
+    /// Creates a grid with every cell clear, with the number of rows and
+    /// columns given by `dimensions`.
+    pub fn new(dimensions: &Position) -> Self {
+        BitGrid {
+            words: vec![0; (dimensions.row * dimensions.column).div_ceil(WORD_BITS)],
+            width: dimensions.column,
+            height: dimensions.row,
+        }
+    }
+
+    /// Creates a grid of the same size as another, with the cells set where
+    /// they satisfy `predicate`.
+    pub fn from_grid<T, P>(grid: &Grid<T>, mut predicate: P) -> Self
+    where
+        P: FnMut(&Position, &T) -> bool,
+    {
+        let mut bits = BitGrid::new(&grid.dimensions());
+        for (position, value) in grid.iter() {
+            if predicate(&position, value) {
+                bits.set(&position);
+            }
+        }
+        bits
+    }
+
+    fn index_of(&self, position: &Position) -> Option<usize> {
+        if position.row < self.height && position.column < self.width {
+            Some(position.row * self.width + position.column)
+        } else {
+            None
+        }
+    }
+
+    pub fn dimensions(&self) -> Position {
+        Position {
+            row: self.height,
+            column: self.width,
+        }
+    }
+
+    /// Whether a cell is set. Cells outside of the grid are never set.
+    pub fn test(&self, position: &Position) -> bool {
+        self.index_of(position)
+            .is_some_and(|index| self.words[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0)
+    }
+
+    /// Sets a cell, returning whether it was previously clear. Positions
+    /// outside of the grid are ignored.
+    pub fn set(&mut self, position: &Position) -> bool {
+        let Some(index) = self.index_of(position) else {
+            return false;
+        };
+        let word = &mut self.words[index / WORD_BITS];
+        let mask = 1 << (index % WORD_BITS);
+        let was_clear = *word & mask == 0;
+        *word |= mask;
+        was_clear
+    }
+
+    /// Clears a cell, returning whether it was previously set. Positions
+    /// outside of the grid are ignored.
+    pub fn clear(&mut self, position: &Position) -> bool {
+        let Some(index) = self.index_of(position) else {
+            return false;
+        };
+        let word = &mut self.words[index / WORD_BITS];
+        let mask = 1 << (index % WORD_BITS);
+        let was_set = *word & mask != 0;
+        *word &= !mask;
+        was_set
+    }
+
+    pub fn clear_all(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// The number of cells which are set.
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Every position which is set, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(|(word_index, &word)| {
+                let mut remaining = word;
+                std::iter::from_fn(move || {
+                    if remaining == 0 {
+                        return None;
+                    }
+                    let bit = remaining.trailing_zeros() as usize;
+                    remaining &= remaining - 1;
+                    Some(word_index * WORD_BITS + bit)
+                })
+            })
+            .map(|index| Position {
+                row: index / self.width,
+                column: index % self.width,
+            })
+    }
+
+    fn combine<F>(&mut self, other: &BitGrid, mut operation: F)
+    where
+        F: FnMut(&mut u64, u64),
+    {
+        assert_eq!(
+            self.dimensions(),
+            other.dimensions(),
+            "bit grids must be the same size to be combined"
+        );
+        for (word, &other) in self.words.iter_mut().zip(&other.words) {
+            operation(word, other);
+        }
+    }
+
+    pub fn print(&self) {
+        for row in 0..self.height {
+            let line: String = (0..self.width)
+                .map(|column| {
+                    if self.test(&Position { row, column }) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            println!("{}", line);
+        }
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, other: &BitGrid) {
+        self.combine(other, |word, other| *word &= other);
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, other: &BitGrid) {
+        self.combine(other, |word, other| *word |= other);
+    }
+}
+
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, other: &BitGrid) {
+        self.combine(other, |word, other| *word ^= other);
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result &= other;
+        result
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result |= other;
+        result
+    }
+}
+
+impl BitXor for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitxor(self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result ^= other;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_sets_and_clears_bits() {
+        let mut bits = BitGrid::new(&Position {
+            row: 10,
+            column: 10,
+        });
+        let position = Position { row: 6, column: 4 };
+
+        assert!(!bits.test(&position));
+        assert!(bits.set(&position));
+        assert!(!bits.set(&position));
+        assert!(bits.test(&position));
+        assert_eq!(bits.count_ones(), 1);
+
+        assert!(bits.clear(&position));
+        assert!(!bits.clear(&position));
+        assert!(bits.is_empty());
+
+        assert!(!bits.set(&Position { row: 10, column: 0 }));
+        assert!(!bits.test(&Position { row: 10, column: 0 }));
+    }
+
+    #[test]
+    fn it_lists_set_positions() {
+        let grid = Grid::from_str(
+            "\
+#..
+...
+.##",
+            |c| c,
+        );
+        let bits = BitGrid::from_grid(&grid, |_, &c| c == '#');
+
+        assert_eq!(
+            bits.positions().collect::<Vec<_>>(),
+            vec![
+                Position { row: 0, column: 0 },
+                Position { row: 2, column: 1 },
+                Position { row: 2, column: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_combines_grids() {
+        let dimensions = Position {
+            row: 3,
+            column: 100,
+        };
+        let mut a = BitGrid::new(&dimensions);
+        let mut b = BitGrid::new(&dimensions);
+        assert_eq!(a.dimensions(), dimensions);
+        for column in 0..100 {
+            a.set(&Position { row: 1, column });
+            b.set(&Position {
+                row: column % 3,
+                column,
+            });
+        }
+
+        assert_eq!((&a & &b).count_ones(), 33);
+        assert_eq!((&a | &b).count_ones(), 167);
+        assert_eq!((&a ^ &b).count_ones(), 134);
+
+        a ^= &a.clone();
+        assert!(a.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_rejects_grids_of_different_sizes() {
+        let mut a = BitGrid::new(&Position { row: 2, column: 2 });
+        a |= &BitGrid::new(&Position { row: 1, column: 4 });
+    }
+}
@@ -0,0 +1,280 @@
+use crate::grid::Grid;
+use crate::position::Position;
+
+/// A grid over a huge coordinate space, in which each run of rows or columns
+/// between the interesting coordinates is squashed into a single cell. Every
+/// interesting coordinate keeps a row or column of its own, and there is an
+/// extra cell of margin around the outside so that flood fills can reach all
+/// the way around whatever is drawn.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CompressedGrid<T> {
+    grid: Grid<T>,
+    /// Compressed row `i` covers the real rows from `rows[i]` up to but not
+    /// including `rows[i + 1]`.
+    rows: Vec<i64>,
+    columns: Vec<i64>,
+}
+
+impl<T: Clone> CompressedGrid<T> {
+    pub fn new<R, C>(rows: R, columns: C, default: T) -> Self
+    where
+        R: IntoIterator<Item = i64>,
+        C: IntoIterator<Item = i64>,
+    {
+        let rows = boundaries(rows);
+        let columns = boundaries(columns);
+        let height = rows.len().saturating_sub(1);
+        let width = columns.len().saturating_sub(1);
+
+        CompressedGrid {
+            grid: Grid::from_vec(vec![default; width * height], width),
+            rows,
+            columns,
+        }
+    }
+
+    /// Builds a grid in which the rows and columns of every point are
+    /// interesting.
+    pub fn from_points<'a, I>(points: I, default: T) -> Self
+    where
+        I: IntoIterator<Item = &'a Position<i64>>,
+    {
+        let (rows, columns): (Vec<i64>, Vec<i64>) =
+            points.into_iter().map(|p| (p.row, p.column)).unzip();
+        CompressedGrid::new(rows, columns, default)
+    }
+}
+
+impl<T> CompressedGrid<T> {
+    /// The compressed cells, for use with any of the usual `Grid` methods.
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn grid_mut(&mut self) -> &mut Grid<T> {
+        &mut self.grid
+    }
+
+    /// Finds the compressed cell which covers a real position, or `None` if
+    /// it is outside of the grid.
+    pub fn compress(&self, position: &Position<i64>) -> Option<Position> {
+        Some(Position {
+            row: locate(&self.rows, position.row)?,
+            column: locate(&self.columns, position.column)?,
+        })
+    }
+
+    /// The real position of the top left of a compressed cell.
+    pub fn origin(&self, position: &Position) -> Option<Position<i64>> {
+        self.grid.get(position)?;
+        Some(Position {
+            row: self.rows[position.row],
+            column: self.columns[position.column],
+        })
+    }
+
+    /// The number of real rows and columns covered by a compressed cell.
+    pub fn size(&self, position: &Position) -> Option<Position<u64>> {
+        self.grid.get(position)?;
+        Some(Position {
+            row: (self.rows[position.row + 1] - self.rows[position.row]) as u64,
+            column: (self.columns[position.column + 1] - self.columns[position.column]) as u64,
+        })
+    }
+
+    /// The number of real cells covered by a compressed cell.
+    pub fn area(&self, position: &Position) -> Option<u64> {
+        self.size(position).map(|size| size.row * size.column)
+    }
+
+    /// The total number of real cells covered by some compressed cells, such
+    /// as the result of a flood fill. Cells outside of the grid are ignored.
+    pub fn area_of<'a, I>(&self, positions: I) -> u64
+    where
+        I: IntoIterator<Item = &'a Position>,
+    {
+        positions
+            .into_iter()
+            .filter_map(|position| self.area(position))
+            .sum()
+    }
+
+    /// The value of the compressed cell which covers a real position.
+    pub fn get(&self, position: &Position<i64>) -> Option<&T> {
+        self.grid.get(&self.compress(position)?)
+    }
+
+    /// Sets every compressed cell which overlaps the rectangle between two
+    /// real corners, inclusive. Cells are only ever set whole, so the corners
+    /// should be made up of interesting coordinates for this to be exact.
+    pub fn fill(&mut self, from: &Position<i64>, to: &Position<i64>, value: T)
+    where
+        T: Clone,
+    {
+        let (Some(start), Some(end)) = (
+            self.compress(&Position {
+                row: from.row.min(to.row),
+                column: from.column.min(to.column),
+            }),
+            self.compress(&Position {
+                row: from.row.max(to.row),
+                column: from.column.max(to.column),
+            }),
+        ) else {
+            return;
+        };
+
+        for row in start.row..=end.row {
+            for column in start.column..=end.column {
+                self.grid.set(&Position { row, column }, value.clone());
+            }
+        }
+    }
+}
+
+/// Splits the line around each coordinate so that it gets a cell of its own,
+/// with a cell of margin before the first and after the last.
+fn boundaries<I: IntoIterator<Item = i64>>(coordinates: I) -> Vec<i64> {
+    let mut boundaries: Vec<i64> = coordinates
+        .into_iter()
+        .flat_map(|coordinate| [coordinate, coordinate + 1])
+        .collect();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    if let (Some(&first), Some(&last)) = (boundaries.first(), boundaries.last()) {
+        boundaries.insert(0, first - 1);
+        boundaries.push(last + 1);
+    }
+    boundaries
+}
+
+fn locate(boundaries: &[i64], coordinate: i64) -> Option<usize> {
+    let index = boundaries.partition_point(|&boundary| boundary <= coordinate);
+    if index == 0 || index == boundaries.len() {
+        None
+    } else {
+        Some(index - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Direction;
+    use crate::grid::regions::Connectivity;
+
+    const DIG_PLAN: &str = "\
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    fn lagoon_area(instructions: &[(Direction, i64)]) -> u64 {
+        let mut corners = vec![Position { row: 0, column: 0 }];
+        for (direction, distance) in instructions {
+            let last = corners.last().unwrap();
+            let corner = match direction {
+                Direction::Up => Position {
+                    row: last.row - distance,
+                    column: last.column,
+                },
+                Direction::Down => Position {
+                    row: last.row + distance,
+                    column: last.column,
+                },
+                Direction::Left => Position {
+                    row: last.row,
+                    column: last.column - distance,
+                },
+                Direction::Right => Position {
+                    row: last.row,
+                    column: last.column + distance,
+                },
+            };
+            corners.push(corner);
+        }
+
+        let mut grid = CompressedGrid::from_points(&corners, false);
+        for pair in corners.windows(2) {
+            grid.fill(&pair[0], &pair[1], true);
+        }
+
+        let outside = grid.grid().flood_fill(
+            &Position { row: 0, column: 0 },
+            Connectivity::Four,
+            |_, &dug| !dug,
+        );
+        let total = grid.area_of(&grid.grid().positions().collect::<Vec<_>>());
+        total - grid.area_of(&outside)
+    }
+
+    #[test]
+    fn it_measures_a_small_lagoon() {
+        let instructions: Vec<(Direction, i64)> = DIG_PLAN
+            .lines()
+            .map(|line| {
+                let mut parts = line.split_whitespace();
+                let direction = match parts.next().unwrap() {
+                    "U" => Direction::Up,
+                    "D" => Direction::Down,
+                    "L" => Direction::Left,
+                    _ => Direction::Right,
+                };
+                (direction, parts.next().unwrap().parse().unwrap())
+            })
+            .collect();
+
+        assert_eq!(lagoon_area(&instructions), 62);
+    }
+
+    #[test]
+    fn it_measures_a_huge_lagoon() {
+        let instructions: Vec<(Direction, i64)> = DIG_PLAN
+            .lines()
+            .map(|line| {
+                let hex = &line[line.len() - 7..line.len() - 1];
+                let direction = match &hex[5..] {
+                    "0" => Direction::Right,
+                    "1" => Direction::Down,
+                    "2" => Direction::Left,
+                    _ => Direction::Up,
+                };
+                (direction, i64::from_str_radix(&hex[..5], 16).unwrap())
+            })
+            .collect();
+
+        assert_eq!(lagoon_area(&instructions), 952408144115);
+    }
+
+    #[test]
+    fn it_maps_between_real_and_compressed_cells() {
+        let grid = CompressedGrid::new([0, 10], [5], 0);
+
+        assert_eq!(grid.grid().dimensions(), Position { row: 5, column: 3 });
+        assert_eq!(
+            grid.compress(&Position { row: 4, column: 5 }),
+            Some(Position { row: 2, column: 1 })
+        );
+        assert_eq!(
+            grid.origin(&Position { row: 2, column: 1 }),
+            Some(Position { row: 1, column: 5 })
+        );
+        assert_eq!(grid.area(&Position { row: 2, column: 1 }), Some(9));
+        assert_eq!(grid.compress(&Position { row: 12, column: 5 }), None);
+        assert_eq!(
+            grid.compress(&Position { row: 0, column: 4 }),
+            Some(Position { row: 1, column: 0 })
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use crate::graph::graph::{Edge, Graph};
+use crate::grid::Grid;
+use crate::position::Position;
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+impl<T> Grid<T> {
+    /// Builds a graph over caller-defined states by exploring outwards from
+    /// the starting states. `expand` lists the states which might follow a
+    /// state, and `cost` gives the weight of moving between two of them, or
+    /// `None` if the move is not allowed. Only reachable states are added.
+    pub fn build_graph<S, I, E, C>(
+        &self,
+        starts: impl IntoIterator<Item = S>,
+        mut expand: E,
+        mut cost: C,
+    ) -> Graph<S>
+    where
+        S: Eq + Hash + Clone,
+        I: IntoIterator<Item = S>,
+        E: FnMut(&Grid<T>, &S) -> I,
+        C: FnMut(&Grid<T>, &S, &S) -> Option<i64>,
+    {
+        let mut graph = Graph::new();
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        for start in starts {
+            if seen.insert(start.clone()) {
+                graph.add_node(start.clone());
+                queue.push_back(start);
+            }
+        }
+
+        while let Some(source) = queue.pop_front() {
+            for destination in expand(self, &source) {
+                let Some(weight) = cost(self, &source, &destination) else {
+                    continue;
+                };
+                if seen.insert(destination.clone()) {
+                    queue.push_back(destination.clone());
+                }
+                graph.add_edge(Edge {
+                    source: source.clone(),
+                    destination,
+                    weight,
+                });
+            }
+        }
+
+        graph
+    }
+
+    /// Builds a graph with a node for every cell which satisfies `passable`
+    /// and an edge between orthogonal neighbours weighted by `cost`.
+    pub fn build_position_graph<P, C>(&self, mut passable: P, mut cost: C) -> Graph<Position>
+    where
+        P: FnMut(&Position, &T) -> bool,
+        C: FnMut(&Position, &Position) -> i64,
+    {
+        let mut graph = Graph::new();
+
+        for (source, value) in self.iter() {
+            if !passable(&source, value) {
+                continue;
+            }
+            graph.add_node(source);
+
+            for (destination, value) in self.neighbours(&source) {
+                if passable(&destination, value) {
+                    let weight = cost(&source, &destination);
+                    graph.add_edge(Edge {
+                        source,
+                        destination,
+                        weight,
+                    });
+                }
+            }
+        }
+
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Direction;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct Reindeer {
+        position: Position,
+        direction: Direction,
+    }
+
+    #[test]
+    fn it_builds_a_graph_over_custom_states() {
+        let grid = Grid::from_str(
+            "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############",
+            |c| c,
+        );
+        let start = Reindeer {
+            position: Position { row: 13, column: 1 },
+            direction: Direction::Right,
+        };
+
+        let mut graph = grid.build_graph(
+            [start.clone()],
+            |grid, state| {
+                grid.neighbours_with_direction(&state.position)
+                    .filter(|(_, _, c)| **c != '#')
+                    .map(|(direction, position, _)| Reindeer {
+                        position,
+                        direction,
+                    })
+                    .collect::<Vec<_>>()
+            },
+            |_, source, destination| {
+                if destination.direction == source.direction {
+                    Some(1)
+                } else if destination.direction == source.direction.turn_clockwise()
+                    || destination.direction == source.direction.turn_anticlockwise()
+                {
+                    Some(1001)
+                } else {
+                    None
+                }
+            },
+        );
+        graph.dijkstra(start).unwrap();
+
+        let finish = Position { row: 1, column: 13 };
+        let distance = Direction::ALL
+            .into_iter()
+            .filter_map(|direction| {
+                graph.get_node_distance(&Reindeer {
+                    position: finish,
+                    direction,
+                })
+            })
+            .min();
+        assert_eq!(distance, Some(7036));
+    }
+
+    #[test]
+    fn it_builds_a_graph_over_positions() {
+        let grid = Grid::from_str(
+            "\
+129
+1#1
+111",
+            |c| c,
+        );
+
+        let mut graph = grid.build_position_graph(
+            |_, &c| c != '#',
+            |_, destination| grid[destination].to_digit(10).unwrap() as i64,
+        );
+        assert_eq!(graph.len(), 8);
+
+        graph.dijkstra(Position { row: 0, column: 0 }).unwrap();
+        assert_eq!(
+            graph.get_node_distance(&Position { row: 1, column: 2 }),
+            Some(5)
+        );
+    }
+}
@@ -0,0 +1,494 @@
+use crate::direction::{Direction, Heading};
+use crate::position::Position;
+use std::convert::Infallible;
+use std::ops::{Index, IndexMut};
+
+pub mod bfs;
+pub mod bits;
+pub mod compressed;
+pub mod graph;
+pub mod parse;
+pub mod ray;
+pub mod regions;
+pub mod search;
+pub mod sparse;
+pub mod view;
+pub mod wrapped;
+
+/// A rectangular grid, stored row by row in a single contiguous buffer.
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from a list of rows. As with `from_vec`, a grid with no
+    /// columns also has no rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows are not all the same length. Use `try_parse` to get
+    /// an error for ragged input instead.
+    pub fn new(data: Vec<Vec<T>>) -> Self {
+        let width = data.first().map_or(0, |row| row.len());
+        assert!(
+            data.iter().all(|row| row.len() == width),
+            "all rows of a grid must have the same length"
+        );
+        Grid {
+            height: if width == 0 { 0 } else { data.len() },
+            data: data.into_iter().flatten().collect(),
+            width,
+        }
+    }
+
+    /// Builds a grid from cells which are already laid out row by row.
+    pub fn from_vec(data: Vec<T>, width: usize) -> Self {
+        let fits = if width == 0 {
+            data.is_empty()
+        } else {
+            data.len().is_multiple_of(width)
+        };
+        assert!(
+            fits,
+            "the number of cells must be a multiple of the grid width"
+        );
+        let height = data.len().checked_div(width).unwrap_or(0);
+        Grid {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Builds a grid with one cell per character of the input. Like
+    /// `try_parse`, carriage returns and trailing blank lines are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lines are not all the same length. Use `try_parse` to
+    /// get an error for ragged input instead.
+    pub fn from_str<F>(input: &str, mut mapper: F) -> Self
+    where
+        F: FnMut(char) -> T,
+    {
+        Grid::try_parse(input, |c| Ok::<T, Infallible>(mapper(c)))
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn index_of(&self, position: &Position<usize>) -> Option<usize> {
+        self.flat_index(position)
+            .filter(|&index| index < self.data.len())
+    }
+
+    /// The index of a position in the buffer, or `None` if its column is
+    /// outside the grid. The row is not checked here: rows past the end are
+    /// clamped so that they land past the end of the buffer, leaving the
+    /// slice lookup which follows as the only check on the row.
+    #[inline]
+    fn flat_index(&self, position: &Position<usize>) -> Option<usize> {
+        if position.column < self.width {
+            Some(position.row.min(self.height) * self.width + position.column)
+        } else {
+            None
+        }
+    }
+
+    pub fn position_of(&self, index: usize) -> Option<Position> {
+        if index < self.data.len() {
+            Some(Position {
+                row: index / self.width,
+                column: index % self.width,
+            })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, position: &Position<usize>) -> Option<&T> {
+        self.data.get(self.flat_index(position)?)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, position: &Position<usize>) -> Option<&mut T> {
+        let index = self.flat_index(position)?;
+        self.data.get_mut(index)
+    }
+
+    pub fn set(&mut self, position: &Position<usize>, value: T) {
+        if let Some(cell) = self.get_mut(position) {
+            *cell = value;
+        }
+    }
+
+    pub fn dimensions(&self) -> Position {
+        Position {
+            row: self.height,
+            column: self.width,
+        }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.rows().enumerate().flat_map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .map(move |(column, cell)| (Position { row, column }, cell))
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        self.data
+            .chunks_mut(self.width.max(1))
+            .enumerate()
+            .flat_map(|(row, cells)| {
+                cells
+                    .iter_mut()
+                    .enumerate()
+                    .map(move |(column, cell)| (Position { row, column }, cell))
+            })
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.iter().map(|(position, _)| position)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` rejects a zero chunk size, but a zero-width grid has no rows
+        self.data.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        if row < self.height {
+            let start = row * self.width;
+            self.data.get(start..start + self.width)
+        } else {
+            None
+        }
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> Option<&mut [T]> {
+        if row < self.height {
+            let start = row * self.width;
+            self.data.get_mut(start..start + self.width)
+        } else {
+            None
+        }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |column| self.column(column))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        let cells = if column < self.width {
+            &self.data[column..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// The cell one step from a position in a heading, if it lies within the
+    /// grid.
+    pub fn neighbour(
+        &self,
+        position: &Position<usize>,
+        heading: Heading,
+    ) -> Option<(Position, &T)> {
+        let neighbour = heading.travel_with_bounds(position, &self.dimensions())?;
+        Some((neighbour, &self[&neighbour]))
+    }
+
+    /// Orthogonal neighbours of a position which lie within the grid, along
+    /// with the direction travelled to reach them.
+    pub fn neighbours_with_direction(
+        &self,
+        position: &Position<usize>,
+    ) -> impl Iterator<Item = (Direction, Position, &T)> {
+        Direction::ALL.into_iter().filter_map(move |direction| {
+            let (neighbour, value) = self.neighbour(position, Heading::from(&direction))?;
+            Some((direction, neighbour, value))
+        })
+    }
+
+    pub fn neighbours(&self, position: &Position<usize>) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours_with_direction(position)
+            .map(|(_, neighbour, value)| (neighbour, value))
+    }
+
+    /// Diagonal neighbours of a position which lie within the grid, along
+    /// with the heading travelled to reach them.
+    pub fn diagonal_neighbours_with_direction(
+        &self,
+        position: &Position<usize>,
+    ) -> impl Iterator<Item = (Heading, Position, &T)> {
+        Heading::DIAGONAL.into_iter().filter_map(move |heading| {
+            let (neighbour, value) = self.neighbour(position, heading)?;
+            Some((heading, neighbour, value))
+        })
+    }
+
+    pub fn diagonal_neighbours(
+        &self,
+        position: &Position<usize>,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        self.diagonal_neighbours_with_direction(position)
+            .map(|(_, neighbour, value)| (neighbour, value))
+    }
+
+    /// All eight neighbours of a position which lie within the grid.
+    pub fn all_neighbours(
+        &self,
+        position: &Position<usize>,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours(position)
+            .chain(self.diagonal_neighbours(position))
+    }
+
+    pub fn print<F>(&self, mut mapper: F)
+    where
+        F: FnMut(&T) -> char,
+    {
+        self.rows().for_each(|row| {
+            println!("{}", row.iter().map(&mut mapper).collect::<String>());
+        });
+    }
+}
+impl<T> Index<&Position<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: &Position<usize>) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("position {:?} is outside of the grid", position))
+    }
+}
+
+impl<T> IndexMut<&Position<usize>> for Grid<T> {
+    fn index_mut(&mut self, position: &Position<usize>) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {:?} is outside of the grid", position))
+    }
+}
+
+impl<T> Index<Position<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position<usize>) -> &T {
+        &self[&position]
+    }
+}
+
+impl<T> IndexMut<Position<usize>> for Grid<T> {
+    fn index_mut(&mut self, position: Position<usize>) -> &mut T {
+        &mut self[&position]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_new_and_dimensions() {
+        let data = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let grid = Grid::new(data);
+
+        assert_eq!(grid.dimensions(), Position { row: 3, column: 3 });
+        assert_eq!(grid.as_slice(), &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_get_and_set() {
+        let mut grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+
+        // Test getting values
+        assert_eq!(grid.get(&Position { row: 0, column: 0 }), Some(&1));
+        assert_eq!(grid.get(&Position { row: 2, column: 2 }), Some(&9));
+        assert_eq!(grid.get(&Position { row: 3, column: 3 }), None);
+        assert_eq!(grid.get(&Position { row: 3, column: 0 }), None);
+        assert_eq!(
+            grid.get(&Position {
+                row: usize::MAX,
+                column: 2
+            }),
+            None
+        );
+
+        // Test setting values
+        grid.set(&Position { row: 1, column: 1 }, 42);
+        assert_eq!(grid.get(&Position { row: 1, column: 1 }), Some(&42));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let input = "\
+123
+456
+789";
+        let grid = Grid::from_str(input, |c| c.to_digit(10).unwrap());
+
+        assert_eq!(grid.dimensions(), Position { row: 3, column: 3 });
+        assert_eq!(grid.as_slice(), &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_iter() {
+        let grid = Grid::new(vec![vec![1, 2], vec![3, 4]]);
+        let cells: Vec<(Position, &i32)> = grid.iter().collect();
+        assert_eq!(
+            cells,
+            vec![
+                (Position { row: 0, column: 0 }, &1),
+                (Position { row: 0, column: 1 }, &2),
+                (Position { row: 1, column: 0 }, &3),
+                (Position { row: 1, column: 1 }, &4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        let rows: Vec<&[i32]> = grid.rows().collect();
+        assert_eq!(rows, vec![&[1, 2, 3][..], &[4, 5, 6][..]]);
+
+        let columns: Vec<Vec<i32>> = grid
+            .columns()
+            .map(|column| column.copied().collect())
+            .collect();
+        assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+
+        let corner: Vec<&i32> = grid
+            .neighbours(&Position { row: 0, column: 0 })
+            .map(|(_, value)| value)
+            .collect();
+        assert_eq!(corner, vec![&2, &4]);
+
+        let centre: Vec<(Direction, &i32)> = grid
+            .neighbours_with_direction(&Position { row: 1, column: 1 })
+            .map(|(direction, _, value)| (direction, value))
+            .collect();
+        assert_eq!(
+            centre,
+            vec![
+                (Direction::Up, &2),
+                (Direction::Right, &6),
+                (Direction::Down, &8),
+                (Direction::Left, &4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagonal_neighbours() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+
+        let corner: Vec<&i32> = grid
+            .diagonal_neighbours(&Position { row: 2, column: 0 })
+            .map(|(_, value)| value)
+            .collect();
+        assert_eq!(corner, vec![&5]);
+
+        let centre: Vec<&i32> = grid
+            .all_neighbours(&Position { row: 1, column: 1 })
+            .map(|(_, value)| value)
+            .collect();
+        assert_eq!(centre, vec![&2, &6, &8, &4, &3, &9, &7, &1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_rejects_ragged_rows() {
+        Grid::new(vec![vec![1, 2, 3], vec![4, 5]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2 has 2 columns but 3 were expected")]
+    fn test_from_str_rejects_ragged_lines() {
+        Grid::from_str("123\n45", |c| c);
+    }
+
+    #[test]
+    fn test_from_str_ignores_trailing_blank_lines() {
+        let grid = Grid::from_str("12\n34\n\n", |c| c.to_digit(10).unwrap());
+
+        assert_eq!(grid.dimensions(), Position { row: 2, column: 2 });
+        assert_eq!(grid.as_slice(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_zero_width_grids_have_no_rows() {
+        let grid: Grid<i32> = Grid::new(vec![vec![], vec![]]);
+        assert_eq!(
+            grid.dimensions(),
+            Grid::<i32>::from_vec(vec![], 0).dimensions()
+        );
+        assert_eq!(grid.dimensions(), Position { row: 0, column: 0 });
+        assert_eq!(grid.rows().count(), 0);
+    }
+
+    #[test]
+    fn test_index_conversions() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        assert_eq!(grid.index_of(&Position { row: 1, column: 2 }), Some(5));
+        assert_eq!(grid.index_of(&Position { row: 0, column: 3 }), None);
+        assert_eq!(grid.index_of(&Position { row: 2, column: 0 }), None);
+        assert_eq!(grid.position_of(4), Some(Position { row: 1, column: 1 }));
+        assert_eq!(grid.position_of(6), None);
+    }
+
+    #[test]
+    fn test_index_by_position() {
+        let mut grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        assert_eq!(grid[Position { row: 1, column: 0 }], 4);
+        grid[&Position { row: 0, column: 2 }] = 30;
+        assert_eq!(grid.row(0), Some(&[1, 2, 30][..]));
+        assert_eq!(grid.row(2), None);
+
+        grid.row_mut(1).unwrap().reverse();
+        assert_eq!(grid.as_slice(), &[1, 2, 30, 6, 5, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let grid = Grid::new(vec![vec![1, 2], vec![3, 4]]);
+        let _ = grid[Position { row: 0, column: 2 }];
+    }
+
+    #[test]
+    fn test_set_out_of_bounds() {
+        let mut grid = Grid::new(vec![vec![1, 2], vec![3, 4]]);
+
+        // Attempt to set a value outside of the grid's bounds
+        grid.set(
+            &Position {
+                row: 10,
+                column: 10,
+            },
+            42,
+        );
+        // Grid should remain unchanged
+        assert_eq!(grid.as_slice(), &[1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,272 @@
+use crate::grid::Grid;
+use crate::position::Position;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ParseError<E> {
+    /// A row whose length differs from the first row of the grid.
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character which the mapper rejected.
+    InvalidCell {
+        position: Position,
+        character: char,
+        error: E,
+    },
+}
+
+impl<E: fmt::Display> fmt::Display for ParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} has {} columns but {} were expected",
+                row + 1,
+                found,
+                expected
+            ),
+            ParseError::InvalidCell {
+                position,
+                character,
+                error,
+            } => write!(
+                f,
+                "invalid character {:?} at line {}, column {}: {}",
+                character,
+                position.row + 1,
+                position.column + 1,
+                error
+            ),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> Error for ParseError<E> {}
+
+/// Where each marker character was found while parsing a grid.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct Markers {
+    positions: HashMap<char, Vec<Position>>,
+}
+
+impl Markers {
+    /// The first position of a marker, for markers like a start or end which
+    /// should only appear once.
+    pub fn get(&self, marker: char) -> Option<&Position> {
+        self.all(marker).first()
+    }
+
+    /// Every position of a marker, row by row.
+    pub fn all(&self, marker: char) -> &[Position] {
+        self.positions
+            .get(&marker)
+            .map_or(&[], |positions| positions)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (char, &[Position])> {
+        self.positions
+            .iter()
+            .map(|(marker, positions)| (*marker, positions.as_slice()))
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses a rectangular grid, one character per cell. Carriage returns
+    /// and trailing blank lines are ignored so that text pasted into a
+    /// browser can be passed straight through.
+    pub fn try_parse<F, E>(input: &str, mut mapper: F) -> Result<Self, ParseError<E>>
+    where
+        F: FnMut(char) -> Result<T, E>,
+    {
+        let mut lines: Vec<&str> = input
+            .lines()
+            .map(|line| line.trim_end_matches('\r'))
+            .collect();
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        let mut data = Vec::with_capacity(width * lines.len());
+
+        for (row, line) in lines.into_iter().enumerate() {
+            let mut found = 0;
+            for (column, character) in line.chars().enumerate() {
+                let cell = mapper(character).map_err(|error| ParseError::InvalidCell {
+                    position: Position { row, column },
+                    character,
+                    error,
+                })?;
+                data.push(cell);
+                found += 1;
+            }
+
+            if found != width {
+                return Err(ParseError::RaggedRow {
+                    row,
+                    expected: width,
+                    found,
+                });
+            }
+        }
+
+        Ok(Grid::from_vec(data, width))
+    }
+
+    /// Like `try_parse`, but records where each of the marker characters
+    /// appears. Markers are not passed to the mapper, and their cells are
+    /// filled with the tile paired with them instead.
+    pub fn try_parse_with_markers<F, E>(
+        input: &str,
+        markers: &[(char, T)],
+        mut mapper: F,
+    ) -> Result<(Self, Markers), ParseError<E>>
+    where
+        T: Clone,
+        F: FnMut(char) -> Result<T, E>,
+    {
+        let mut found: Vec<(char, usize)> = vec![];
+        let mut index = 0;
+        let grid = Grid::try_parse(input, |character| {
+            let cell = match markers.iter().find(|(marker, _)| *marker == character) {
+                Some((marker, tile)) => {
+                    found.push((*marker, index));
+                    Ok(tile.clone())
+                }
+                None => mapper(character),
+            };
+            index += 1;
+            cell
+        })?;
+
+        let mut markers = Markers::default();
+        for (marker, index) in found {
+            let position = grid.position_of(index).unwrap();
+            markers.positions.entry(marker).or_default().push(position);
+        }
+
+        Ok((grid, markers))
+    }
+
+    /// Like `try_parse_with_markers`, for mappers which accept any character.
+    /// Panics if the rows of the grid are not all the same length.
+    pub fn parse_with_markers<F>(
+        input: &str,
+        markers: &[(char, T)],
+        mut mapper: F,
+    ) -> (Self, Markers)
+    where
+        T: Clone,
+        F: FnMut(char) -> T,
+    {
+        Grid::try_parse_with_markers(input, markers, |c| Ok::<T, Infallible>(mapper(c)))
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_digit(c: char) -> Result<u32, String> {
+        c.to_digit(10).ok_or_else(|| String::from("not a digit"))
+    }
+
+    #[test]
+    fn it_parses_a_grid() {
+        let grid = Grid::try_parse("12\r\n34\r\n\r\n\n", parse_digit).unwrap();
+
+        assert_eq!(grid.dimensions(), Position { row: 2, column: 2 });
+        assert_eq!(grid.as_slice(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn it_parses_an_empty_grid() {
+        let grid = Grid::try_parse("\n\n", parse_digit).unwrap();
+        assert_eq!(grid.dimensions(), Position { row: 0, column: 0 });
+    }
+
+    #[test]
+    fn it_extracts_markers() {
+        let (grid, markers) = Grid::parse_with_markers(
+            "\
+#####
+#S.E#
+#####",
+            &[('S', '.'), ('E', '.')],
+            |c| c,
+        );
+
+        assert_eq!(markers.get('S'), Some(&Position { row: 1, column: 1 }));
+        assert_eq!(markers.get('E'), Some(&Position { row: 1, column: 3 }));
+        assert_eq!(markers.get('@'), None);
+        assert_eq!(grid[Position { row: 1, column: 1 }], '.');
+        assert_eq!(grid[Position { row: 1, column: 3 }], '.');
+    }
+
+    #[test]
+    fn it_extracts_repeated_markers() {
+        let (grid, markers) = Grid::try_parse_with_markers(
+            "\
+0.1
+.0.
+",
+            &[('0', 0), ('1', 0)],
+            |c| if c == '.' { Ok(9) } else { Err("unexpected") },
+        )
+        .unwrap();
+
+        assert_eq!(grid.as_slice(), &[0, 9, 0, 9, 0, 9]);
+        assert_eq!(
+            markers.all('0'),
+            &[
+                Position { row: 0, column: 0 },
+                Position { row: 1, column: 1 }
+            ]
+        );
+        assert_eq!(markers.all('1'), &[Position { row: 0, column: 2 }]);
+        assert_eq!(markers.iter().count(), 2);
+    }
+
+    #[test]
+    fn it_rejects_ragged_rows() {
+        let result = Grid::try_parse("123\n45\n678", parse_digit);
+
+        assert_eq!(
+            result.err(),
+            Some(ParseError::RaggedRow {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_cells() {
+        let error = Grid::try_parse("123\n4x6", parse_digit).err().unwrap();
+
+        assert_eq!(
+            error,
+            ParseError::InvalidCell {
+                position: Position { row: 1, column: 1 },
+                character: 'x',
+                error: String::from("not a digit"),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "invalid character 'x' at line 2, column 2: not a digit"
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use crate::grid::Grid;
+use crate::position::Position;
+
+/// The cells visited by repeatedly adding a step to a position, ending at the
+/// edge of the grid.
+#[derive(Clone, Debug)]
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    position: Option<Position>,
+    step: Position<isize>,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Position, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.position.take()?;
+        let next = current.checked_offset(&self.step, &self.grid.dimensions())?;
+        self.position = Some(next);
+        Some((next, &self.grid[&next]))
+    }
+}
+
+impl<T> Grid<T> {
+    /// Casts a ray from a position along an integer step, such as
+    /// `Heading::offset` or the difference between two positions. The start
+    /// is not included, so the first cell is one step away from it.
+    pub fn ray(&self, start: &Position, step: &Position<isize>) -> Ray<'_, T> {
+        assert!(
+            step.row != 0 || step.column != 0,
+            "a ray must have a non-zero step"
+        );
+        Ray {
+            grid: self,
+            position: Some(*start),
+            step: *step,
+        }
+    }
+
+    /// Like `ray`, but also stops just before the first cell which satisfies
+    /// `blocked`.
+    pub fn ray_until<'a, B>(
+        &'a self,
+        start: &Position,
+        step: &Position<isize>,
+        mut blocked: B,
+    ) -> impl Iterator<Item = (Position, &'a T)> + use<'a, T, B>
+    where
+        B: FnMut(&Position, &T) -> bool,
+    {
+        self.ray(start, step)
+            .take_while(move |(position, value)| !blocked(position, value))
+    }
+
+    /// The first cell along a ray which satisfies `hit`, or `None` if the ray
+    /// leaves the grid without hitting anything.
+    pub fn first_hit<H>(
+        &self,
+        start: &Position,
+        step: &Position<isize>,
+        mut hit: H,
+    ) -> Option<(Position, &T)>
+    where
+        H: FnMut(&Position, &T) -> bool,
+    {
+        self.ray(start, step)
+            .find(|(position, value)| hit(position, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Heading;
+
+    fn lab() -> Grid<char> {
+        Grid::from_str(
+            "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...",
+            |c| c,
+        )
+    }
+
+    #[test]
+    fn it_casts_rays_to_the_edge() {
+        let grid = lab();
+        let start = Position { row: 6, column: 4 };
+
+        let cells: Vec<_> = grid.ray(&start, &Heading::Right.offset()).collect();
+        assert_eq!(cells.len(), 5);
+        assert_eq!(cells[0], (Position { row: 6, column: 5 }, &'.'));
+        assert_eq!(cells[4].0, Position { row: 6, column: 9 });
+
+        let step = Position { row: -2, column: 3 };
+        let cells: Vec<_> = grid.ray(&start, &step).map(|(p, _)| p).collect();
+        assert_eq!(cells, vec![Position { row: 4, column: 7 }]);
+    }
+
+    #[test]
+    fn it_stops_at_blocking_cells() {
+        let grid = lab();
+        let start = Position { row: 6, column: 4 };
+
+        let walked: Vec<_> = grid
+            .ray_until(&start, &Heading::Up.offset(), |_, &c| c == '#')
+            .collect();
+        assert_eq!(walked.len(), 5);
+        assert_eq!(walked.last().unwrap().0, Position { row: 1, column: 4 });
+
+        assert_eq!(
+            grid.first_hit(&start, &Heading::Up.offset(), |_, &c| c == '#'),
+            Some((Position { row: 0, column: 4 }, &'#'))
+        );
+        assert_eq!(
+            grid.first_hit(&start, &Heading::Right.offset(), |_, &c| c == '#'),
+            None
+        );
+    }
+}
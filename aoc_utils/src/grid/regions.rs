@@ -0,0 +1,508 @@
+use crate::direction::{Direction, Heading};
+use crate::grid::Grid;
+use crate::position::Position;
+use crate::rect::Rect;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum Connectivity {
+    /// Cells touch along an edge.
+    Four,
+    /// Cells touch along an edge or at a corner.
+    Eight,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Region {
+    pub label: usize,
+    pub cells: Vec<Position>,
+    /// The smallest rectangle which contains every cell of the region.
+    pub bounds: Rect,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of cell edges which separate the region from the cells
+    /// around it, including the edges of any holes.
+    pub fn perimeter(&self) -> usize {
+        let cells = self.cell_set();
+        self.cells
+            .iter()
+            .flat_map(|cell| Heading::ORTHOGONAL.map(|heading| (cell, heading.offset())))
+            .filter(|(cell, offset)| !self.contains(&cells, cell, offset))
+            .count()
+    }
+
+    /// The number of corners on the outline of the region, including the
+    /// outlines of any holes. A cell which only touches the region at a
+    /// corner counts as outside of it.
+    pub fn corners(&self) -> usize {
+        let cells = self.cell_set();
+        self.cells
+            .iter()
+            .map(|cell| {
+                Heading::DIAGONAL
+                    .iter()
+                    .filter(|heading| {
+                        let diagonal = heading.offset();
+                        let vertical = Position {
+                            column: 0,
+                            ..diagonal
+                        };
+                        let horizontal = Position { row: 0, ..diagonal };
+
+                        let vertical_inside = self.contains(&cells, cell, &vertical);
+                        let horizontal_inside = self.contains(&cells, cell, &horizontal);
+                        let diagonal_inside = self.contains(&cells, cell, &diagonal);
+
+                        let convex = !vertical_inside && !horizontal_inside;
+                        let concave = vertical_inside && horizontal_inside && !diagonal_inside;
+                        convex || concave
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The number of straight sides on the outline of the region. Every side
+    /// ends at a corner, so this is the same as the number of corners.
+    pub fn sides(&self) -> usize {
+        self.corners()
+    }
+
+    /// The outline of the region as closed polygons through the corners of
+    /// its cells, where the cell at `(row, column)` spans from corner
+    /// `(row, column)` to corner `(row + 1, column + 1)`. Outer outlines run
+    /// clockwise and holes run anticlockwise. Outlines are listed in order of
+    /// their first vertex, so the first one is always an outer outline. Only
+    /// the vertices where an outline turns are listed.
+    ///
+    /// Where the outline touches itself at a corner, the walk keeps tight to
+    /// the region's cells. So holes which touch at a corner share a single
+    /// outline, while a region whose cells only meet diagonally, as with
+    /// `Connectivity::Eight`, has a separate clockwise outline for each piece.
+    pub fn boundary(&self) -> Vec<Vec<Position>> {
+        let cells = self.cell_set();
+
+        // Walk each exposed cell edge keeping the region on the right
+        let mut edges: Vec<(Position, Direction, Position)> = vec![];
+        for cell in &self.cells {
+            let Position { row, column } = *cell;
+            for side in Direction::ALL {
+                if self.contains(&cells, cell, &Heading::from(&side).offset()) {
+                    continue;
+                }
+                let (start, end) = match side {
+                    Direction::Up => ((row, column), (row, column + 1)),
+                    Direction::Right => ((row, column + 1), (row + 1, column + 1)),
+                    Direction::Down => ((row + 1, column + 1), (row + 1, column)),
+                    Direction::Left => ((row + 1, column), (row, column)),
+                };
+                edges.push((
+                    Position {
+                        row: start.0,
+                        column: start.1,
+                    },
+                    side.turn_clockwise(),
+                    Position {
+                        row: end.0,
+                        column: end.1,
+                    },
+                ));
+            }
+        }
+        edges.sort_by_key(|(start, _, _)| (start.row, start.column));
+
+        let mut outgoing: HashMap<Position, Vec<usize>> = HashMap::new();
+        for (index, (start, _, _)) in edges.iter().enumerate() {
+            outgoing.entry(*start).or_default().push(index);
+        }
+
+        let mut used = vec![false; edges.len()];
+        let mut outlines = vec![];
+
+        for first in 0..edges.len() {
+            if used[first] {
+                continue;
+            }
+
+            let mut outline: Vec<(Position, Direction)> = vec![];
+            let mut current = first;
+            loop {
+                used[current] = true;
+                let (start, direction, end) = &edges[current];
+                outline.push((*start, direction.clone()));
+
+                // Where the outline pinches together at a vertex, turning
+                // clockwise first keeps it tight around the region's cells
+                let preferences = [
+                    direction.turn_clockwise(),
+                    direction.clone(),
+                    direction.turn_anticlockwise(),
+                ];
+                let candidates = outgoing.get(end).map_or(&[][..], |c| c.as_slice());
+                let next = preferences.iter().find_map(|preference| {
+                    candidates
+                        .iter()
+                        .copied()
+                        .find(|&index| !used[index] && edges[index].1 == *preference)
+                });
+                match next {
+                    Some(index) => current = index,
+                    None => break,
+                }
+            }
+
+            let vertices = (0..outline.len())
+                .filter(|&index| {
+                    let previous = (index + outline.len() - 1) % outline.len();
+                    outline[index].1 != outline[previous].1
+                })
+                .map(|index| outline[index].0)
+                .collect();
+            outlines.push(vertices);
+        }
+
+        outlines
+    }
+
+    fn cell_set(&self) -> HashSet<&Position> {
+        self.cells.iter().collect()
+    }
+
+    /// Whether the cell an offset away from another is in the region. Cells
+    /// outside the bounds of the region cannot be, so the bounds also serve
+    /// as the boundary when stepping.
+    fn contains(
+        &self,
+        cells: &HashSet<&Position>,
+        cell: &Position,
+        offset: &Position<isize>,
+    ) -> bool {
+        cell.checked_offset(offset, &self.bounds)
+            .is_some_and(|neighbour| cells.contains(&neighbour))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn connected_neighbours(
+        &self,
+        position: &Position<usize>,
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        let diagonals = self
+            .diagonal_neighbours(position)
+            .filter(move |_| connectivity == Connectivity::Eight);
+        self.neighbours(position).chain(diagonals)
+    }
+
+    /// Finds every cell reachable from the seed by only stepping onto cells
+    /// which satisfy the predicate. The seed itself must also satisfy it.
+    pub fn flood_fill<P>(
+        &self,
+        seed: &Position<usize>,
+        connectivity: Connectivity,
+        mut predicate: P,
+    ) -> Vec<Position>
+    where
+        P: FnMut(&Position, &T) -> bool,
+    {
+        let mut visited = Grid::from_vec(vec![false; self.data.len()], self.width);
+        let mut filled = vec![];
+
+        match self.get(seed) {
+            Some(value) if predicate(seed, value) => {
+                visited.set(seed, true);
+            }
+            _ => return filled,
+        }
+
+        let mut queue = VecDeque::from([*seed]);
+        while let Some(position) = queue.pop_front() {
+            for (neighbour, value) in self.connected_neighbours(&position, connectivity) {
+                if !visited[&neighbour] && predicate(&neighbour, value) {
+                    visited.set(&neighbour, true);
+                    queue.push_back(neighbour);
+                }
+            }
+            filled.push(position);
+        }
+
+        filled
+    }
+
+    /// Splits the grid into connected regions, where two neighbouring cells
+    /// belong to the same region if `same_region` holds for their values.
+    /// Returns a grid of region labels alongside the regions themselves,
+    /// with each label being an index into the list of regions.
+    pub fn label_regions<F>(
+        &self,
+        connectivity: Connectivity,
+        mut same_region: F,
+    ) -> (Grid<usize>, Vec<Region>)
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut labels = Grid::from_vec(vec![usize::MAX; self.data.len()], self.width);
+        let mut regions = vec![];
+
+        for seed in self.positions() {
+            if labels[&seed] != usize::MAX {
+                continue;
+            }
+
+            let label = regions.len();
+            labels.set(&seed, label);
+
+            let mut cells = vec![];
+            let mut queue = VecDeque::from([seed]);
+
+            while let Some(position) = queue.pop_front() {
+                let current = &self[&position];
+                for (neighbour, value) in self.connected_neighbours(&position, connectivity) {
+                    if labels[&neighbour] == usize::MAX && same_region(current, value) {
+                        labels.set(&neighbour, label);
+                        queue.push_back(neighbour);
+                    }
+                }
+
+                cells.push(position);
+            }
+
+            regions.push(Region {
+                label,
+                bounds: Rect::bounding(&cells).unwrap(),
+                cells,
+            });
+        }
+
+        (labels, regions)
+    }
+
+    /// Splits the grid into connected regions of equal values.
+    pub fn regions(&self, connectivity: Connectivity) -> (Grid<usize>, Vec<Region>)
+    where
+        T: PartialEq,
+    {
+        self.label_regions(connectivity, |a, b| a == b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn garden() -> Grid<char> {
+        Grid::from_str(
+            "\
+AAAA
+BBCD
+BBCC
+EEEC",
+            |c| c,
+        )
+    }
+
+    #[test]
+    fn it_flood_fills() {
+        let grid = Grid::from_str(
+            "\
+#...
+#.#.
+##..",
+            |c| c,
+        );
+        let seed = Position { row: 0, column: 1 };
+
+        let mut filled = grid.flood_fill(&seed, Connectivity::Four, |_, &c| c == '.');
+        filled.sort_by_key(|p| (p.row, p.column));
+        assert_eq!(filled.len(), 7);
+        assert_eq!(filled[0], seed);
+
+        let blocked = grid.flood_fill(
+            &Position { row: 0, column: 0 },
+            Connectivity::Four,
+            |_, &c| c == '.',
+        );
+        assert!(blocked.is_empty());
+    }
+
+    #[test]
+    fn it_flood_fills_diagonally() {
+        let grid = Grid::from_str(
+            "\
+#..
+.#.
+..#",
+            |c| c,
+        );
+        let seed = Position { row: 0, column: 0 };
+
+        let four = grid.flood_fill(&seed, Connectivity::Four, |_, &c| c == '#');
+        assert_eq!(four.len(), 1);
+
+        let eight = grid.flood_fill(&seed, Connectivity::Eight, |_, &c| c == '#');
+        assert_eq!(eight.len(), 3);
+    }
+
+    #[test]
+    fn it_labels_regions() {
+        let grid = garden();
+        let (labels, regions) = grid.regions(Connectivity::Four);
+
+        let areas: Vec<(char, usize)> = regions
+            .iter()
+            .map(|region| (grid[&region.cells[0]], region.area()))
+            .collect();
+        assert_eq!(
+            areas,
+            vec![('A', 4), ('B', 4), ('C', 4), ('D', 1), ('E', 3)]
+        );
+
+        assert_eq!(labels[Position { row: 3, column: 3 }], 2);
+        assert_eq!(
+            regions[2].bounds,
+            Rect::new(
+                Position { row: 1, column: 2 },
+                Position { row: 4, column: 4 }
+            )
+        );
+    }
+
+    #[test]
+    fn it_measures_perimeter_and_sides() {
+        let grid = garden();
+        let (_, regions) = grid.regions(Connectivity::Four);
+
+        let measurements: Vec<(usize, usize)> = regions
+            .iter()
+            .map(|region| (region.perimeter(), region.sides()))
+            .collect();
+        assert_eq!(measurements, vec![(10, 4), (8, 4), (10, 8), (4, 4), (8, 4)]);
+    }
+
+    #[test]
+    fn it_counts_the_sides_of_holes() {
+        let grid = Grid::from_str(
+            "\
+OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO",
+            |c| c,
+        );
+        let (_, regions) = grid.regions(Connectivity::Four);
+
+        assert_eq!(regions[0].area(), 21);
+        assert_eq!(regions[0].perimeter(), 36);
+        assert_eq!(regions[0].sides(), 20);
+    }
+
+    #[test]
+    fn it_traces_the_boundary() {
+        let grid = garden();
+        let (_, regions) = grid.regions(Connectivity::Four);
+
+        assert_eq!(
+            regions[2].boundary(),
+            vec![vec![
+                Position { row: 1, column: 2 },
+                Position { row: 1, column: 3 },
+                Position { row: 2, column: 3 },
+                Position { row: 2, column: 4 },
+                Position { row: 4, column: 4 },
+                Position { row: 4, column: 3 },
+                Position { row: 3, column: 3 },
+                Position { row: 3, column: 2 },
+            ]]
+        );
+    }
+
+    #[test]
+    fn it_traces_holes_which_touch_at_a_corner() {
+        let grid = Grid::from_str(
+            "\
+AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA",
+            |c| c,
+        );
+        let (_, regions) = grid.regions(Connectivity::Four);
+        let outlines = regions[0].boundary();
+
+        assert_eq!(regions[0].sides(), 12);
+        assert_eq!(
+            outlines
+                .iter()
+                .map(|outline| outline.len())
+                .collect::<Vec<_>>(),
+            vec![4, 8]
+        );
+    }
+
+    #[test]
+    fn it_traces_regions_pinched_at_a_diagonal() {
+        let grid = Grid::from_str(
+            "\
+X..
+.X.
+...",
+            |c| c,
+        );
+        let (_, regions) = grid.regions(Connectivity::Eight);
+
+        // Both pieces of the region get an outline of their own, and both
+        // run clockwise
+        assert_eq!(
+            regions[0].boundary(),
+            vec![
+                vec![
+                    Position { row: 0, column: 0 },
+                    Position { row: 0, column: 1 },
+                    Position { row: 1, column: 1 },
+                    Position { row: 1, column: 0 },
+                ],
+                vec![
+                    Position { row: 1, column: 1 },
+                    Position { row: 1, column: 2 },
+                    Position { row: 2, column: 2 },
+                    Position { row: 2, column: 1 },
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn it_labels_regions_with_eight_connectivity() {
+        let grid = Grid::from_str(
+            "\
+X.X
+.X.
+...",
+            |c| c,
+        );
+        let (_, regions) = grid.label_regions(Connectivity::Eight, |a, b| a == b);
+
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].area(), 3);
+        assert_eq!(regions[1].area(), 6);
+    }
+
+    #[test]
+    fn it_compares_neighbouring_cells_rather_than_the_seed() {
+        let grid = Grid::new(vec![vec![1, 2, 3, 4, 9]]);
+        let (labels, regions) =
+            grid.label_regions(Connectivity::Four, |a: &i32, b| (a - b).abs() <= 1);
+
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].area(), 4);
+        assert_eq!(labels[Position { row: 0, column: 4 }], 1);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::direction::Heading;
+use crate::grid::Grid;
+use crate::grid::view::{GridView, Orientation};
+use crate::position::Position;
+
+impl<T> Grid<T> {
+    /// Finds every place where the sequence can be read in a straight line
+    /// in one of the given headings. Returns the position of the first
+    /// element of each match along with the heading it was read in.
+    pub fn find_sequence(&self, sequence: &[T], headings: &[Heading]) -> Vec<(Position, Heading)>
+    where
+        T: PartialEq,
+    {
+        let Some(first) = sequence.first() else {
+            return vec![];
+        };
+        let dimensions = self.dimensions();
+
+        self.iter()
+            .filter(|(_, value)| *value == first)
+            .flat_map(|(anchor, _)| {
+                headings.iter().filter_map(move |heading| {
+                    let is_match = sequence.iter().enumerate().skip(1).all(|(n, expected)| {
+                        heading
+                            .travel_n_with_bounds(&anchor, &dimensions, n)
+                            .is_some_and(|position| self[&position] == *expected)
+                    });
+                    is_match.then_some((anchor, *heading))
+                })
+            })
+            .collect()
+    }
+
+    /// Finds every place where a stencil matches the grid in any of the given
+    /// orientations. `None` cells of the stencil match anything. Returns the
+    /// top-left position of each match along with the orientation of the
+    /// stencil which matched there. Orientations which leave the stencil
+    /// unchanged from an earlier one are skipped, so that symmetric stencils
+    /// are not reported more than once.
+    pub fn find_pattern(
+        &self,
+        stencil: &Grid<Option<T>>,
+        orientations: &[Orientation],
+    ) -> Vec<(Position, Orientation)>
+    where
+        T: PartialEq,
+    {
+        let mut distinct: Vec<GridView<'_, Option<T>>> = vec![];
+        for orientation in orientations {
+            let view = GridView::new(stencil, *orientation);
+            let is_repeat = distinct.iter().any(|other| {
+                other.dimensions() == view.dimensions()
+                    && other.iter().zip(view.iter()).all(|((_, a), (_, b))| a == b)
+            });
+            if !is_repeat {
+                distinct.push(view);
+            }
+        }
+
+        let dimensions = self.dimensions();
+        let mut matches = vec![];
+        for view in distinct {
+            let size = view.dimensions();
+            if size.row > dimensions.row || size.column > dimensions.column {
+                continue;
+            }
+
+            for row in 0..=dimensions.row - size.row {
+                for column in 0..=dimensions.column - size.column {
+                    let anchor = Position { row, column };
+                    let is_match = view.iter().all(|(offset, expected)| match expected {
+                        Some(expected) => self.get(&(anchor + offset)) == Some(expected),
+                        None => true,
+                    });
+                    if is_match {
+                        matches.push((anchor, view.orientation()));
+                    }
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word_search() -> Grid<char> {
+        Grid::from_str(
+            "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX",
+            |c| c,
+        )
+    }
+
+    fn stencil(input: &str) -> Grid<Option<char>> {
+        Grid::from_str(input, |c| if c == '.' { None } else { Some(c) })
+    }
+
+    #[test]
+    fn it_finds_sequences_in_every_direction() {
+        let grid = word_search();
+        let word: Vec<char> = "XMAS".chars().collect();
+
+        let matches = grid.find_sequence(&word, &Heading::ALL);
+        assert_eq!(matches.len(), 18);
+        assert!(matches.contains(&(Position { row: 0, column: 5 }, Heading::Right)));
+        assert!(matches.contains(&(Position { row: 9, column: 5 }, Heading::UpLeft)));
+
+        let orthogonal = grid.find_sequence(&word, &Heading::ORTHOGONAL);
+        assert_eq!(orthogonal.len(), 8);
+    }
+
+    #[test]
+    fn it_finds_stencils_in_every_orientation() {
+        let grid = word_search();
+        let cross = stencil(
+            "\
+M.S
+.A.
+M.S",
+        );
+
+        let matches = grid.find_pattern(&cross, &Orientation::ALL);
+        assert_eq!(matches.len(), 9);
+        assert!(matches.contains(&(Position { row: 0, column: 1 }, Orientation::IDENTITY)));
+    }
+
+    #[test]
+    fn it_finds_stencils_in_a_fixed_orientation() {
+        let grid = Grid::from_str(
+            "\
+#####
+.###.
+..#..",
+            |c| c,
+        );
+        let lock = stencil(
+            "\
+#
+#",
+        );
+
+        let matches = grid.find_pattern(&lock, &[Orientation::IDENTITY]);
+        assert_eq!(matches.len(), 4);
+
+        let rotated = grid.find_pattern(&lock, &Orientation::ROTATIONS);
+        assert_eq!(rotated.len(), 4 + 6);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::grid::Grid;
+use crate::position::Position;
+use crate::rect::Rect;
+use std::collections::HashMap;
+
+/// An unbounded grid which only stores the cells that have been set. Every
+/// other cell reads as the default value.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Position<i64>, T>,
+    default: T,
+    bounds: Option<Rect<i64>>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, position: &Position<i64>) -> bool {
+        self.cells.contains_key(position)
+    }
+
+    pub fn get(&self, position: &Position<i64>) -> &T {
+        self.cells.get(position).unwrap_or(&self.default)
+    }
+
+    /// Returns the cell at a position, storing a copy of the default value
+    /// there first if it has not been set.
+    pub fn get_mut(&mut self, position: &Position<i64>) -> &mut T
+    where
+        T: Clone,
+    {
+        self.extend_bounds(position);
+        self.cells
+            .entry(*position)
+            .or_insert_with(|| self.default.clone())
+    }
+
+    pub fn set(&mut self, position: &Position<i64>, value: T) {
+        self.extend_bounds(position);
+        self.cells.insert(*position, value);
+    }
+
+    pub fn remove(&mut self, position: &Position<i64>) -> Option<T> {
+        let value = self.cells.remove(position)?;
+
+        // Only a cell on the edge of the bounding box can shrink it
+        if let Some(Rect { min, max }) = &self.bounds
+            && (position.row == min.row
+                || position.row == max.row - 1
+                || position.column == min.column
+                || position.column == max.column - 1)
+        {
+            self.recalculate_bounds();
+        }
+        Some(value)
+    }
+
+    /// The smallest rectangle which contains every stored cell.
+    pub fn bounds(&self) -> Option<Rect<i64>> {
+        self.bounds
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Position<i64>, &T)> {
+        self.cells.iter()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = &Position<i64>> {
+        self.cells.keys()
+    }
+
+    /// Copies the bounding box into a dense grid, filling unset cells with
+    /// the default value. Also returns the position which became `(0, 0)`.
+    pub fn to_grid(&self) -> (Grid<T>, Position<i64>)
+    where
+        T: Clone,
+    {
+        let Some(Rect { min, max }) = &self.bounds else {
+            return (Grid::new(vec![]), Position { row: 0, column: 0 });
+        };
+
+        let data = (min.row..max.row)
+            .map(|row| {
+                (min.column..max.column)
+                    .map(|column| self.get(&Position { row, column }).clone())
+                    .collect()
+            })
+            .collect();
+        (Grid::new(data), *min)
+    }
+
+    pub fn print<F>(&self, mut mapper: F)
+    where
+        F: FnMut(&T) -> char,
+    {
+        if let Some(Rect { min, max }) = &self.bounds {
+            (min.row..max.row).for_each(|row| {
+                let line: String = (min.column..max.column)
+                    .map(|column| mapper(self.get(&Position { row, column })))
+                    .collect();
+                println!("{}", line);
+            });
+        }
+    }
+
+    fn extend_bounds(&mut self, position: &Position<i64>) {
+        self.bounds = Some(extend_bounds(self.bounds.take(), position));
+    }
+
+    fn recalculate_bounds(&mut self) {
+        self.bounds = self.cells.keys().fold(None, |bounds, position| {
+            Some(extend_bounds(bounds, position))
+        });
+    }
+}
+
+fn extend_bounds(bounds: Option<Rect<i64>>, position: &Position<i64>) -> Rect<i64> {
+    let cell = Rect::new(
+        *position,
+        Position {
+            row: position.row + 1,
+            column: position.column + 1,
+        },
+    );
+    bounds.map_or(cell, |bounds| bounds.union(&cell))
+}
+
+impl<T: Default> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid::new(T::default())
+    }
+}
+
+impl<T> Extend<(Position<i64>, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Position<i64>, T)>>(&mut self, iter: I) {
+        iter.into_iter()
+            .for_each(|(position, value)| self.set(&position, value));
+    }
+}
+
+impl<T: Default> FromIterator<(Position<i64>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Position<i64>, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::default();
+        grid.extend(iter);
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reads_the_default_for_unset_cells() {
+        let mut grid = SparseGrid::new('.');
+        grid.set(&Position { row: -5, column: 3 }, '#');
+
+        assert_eq!(grid.get(&Position { row: -5, column: 3 }), &'#');
+        assert_eq!(
+            grid.get(&Position {
+                row: 100,
+                column: 0
+            }),
+            &'.'
+        );
+        assert_eq!(grid.len(), 1);
+    }
+
+    #[test]
+    fn it_tracks_the_bounding_box() {
+        let mut grid = SparseGrid::new(0);
+        assert_eq!(grid.bounds(), None);
+
+        grid.set(&Position { row: 2, column: -1 }, 1);
+        grid.set(&Position { row: -3, column: 4 }, 2);
+        *grid.get_mut(&Position { row: 0, column: 7 }) += 3;
+        assert_eq!(
+            grid.bounds(),
+            Some(Rect::new(
+                Position {
+                    row: -3,
+                    column: -1
+                },
+                Position { row: 3, column: 8 }
+            ))
+        );
+
+        grid.remove(&Position { row: 0, column: 7 });
+        assert_eq!(
+            grid.bounds(),
+            Some(Rect::new(
+                Position {
+                    row: -3,
+                    column: -1
+                },
+                Position { row: 3, column: 5 }
+            ))
+        );
+    }
+
+    #[test]
+    fn it_converts_to_a_dense_grid() {
+        let grid: SparseGrid<u8> = [
+            (Position { row: -1, column: 0 }, 1),
+            (Position { row: 0, column: 2 }, 2),
+        ]
+        .into_iter()
+        .collect();
+
+        let (dense, origin) = grid.to_grid();
+        assert_eq!(origin, Position { row: -1, column: 0 });
+        assert_eq!(dense.dimensions(), Position { row: 2, column: 3 });
+        assert_eq!(dense.as_slice(), &[1, 0, 0, 0, 0, 2]);
+    }
+}
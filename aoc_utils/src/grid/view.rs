@@ -0,0 +1,444 @@
+use crate::grid::Grid;
+use crate::position::Position;
+
+/// One of the eight symmetries of a rectangle, expressed as optional row and
+/// column flips followed by an optional transpose.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub struct Orientation {
+    pub transposed: bool,
+    pub rows_flipped: bool,
+    pub columns_flipped: bool,
+}
+
+impl Orientation {
+    pub const IDENTITY: Orientation = Orientation {
+        transposed: false,
+        rows_flipped: false,
+        columns_flipped: false,
+    };
+
+    /// The four rotations, in clockwise order from the identity.
+    pub const ROTATIONS: [Orientation; 4] = [
+        Orientation::IDENTITY,
+        Orientation {
+            transposed: true,
+            rows_flipped: false,
+            columns_flipped: true,
+        },
+        Orientation {
+            transposed: false,
+            rows_flipped: true,
+            columns_flipped: true,
+        },
+        Orientation {
+            transposed: true,
+            rows_flipped: true,
+            columns_flipped: false,
+        },
+    ];
+
+    /// All eight symmetries: the four rotations followed by their mirror
+    /// images.
+    pub const ALL: [Orientation; 8] = [
+        Orientation::ROTATIONS[0],
+        Orientation::ROTATIONS[1],
+        Orientation::ROTATIONS[2],
+        Orientation::ROTATIONS[3],
+        Orientation {
+            transposed: false,
+            rows_flipped: false,
+            columns_flipped: true,
+        },
+        Orientation {
+            transposed: true,
+            rows_flipped: false,
+            columns_flipped: false,
+        },
+        Orientation {
+            transposed: false,
+            rows_flipped: true,
+            columns_flipped: false,
+        },
+        Orientation {
+            transposed: true,
+            rows_flipped: true,
+            columns_flipped: true,
+        },
+    ];
+
+    pub fn transpose(self) -> Self {
+        // Flipping rows before a transpose is equivalent to flipping columns
+        // after it, so the flips swap over.
+        Orientation {
+            transposed: !self.transposed,
+            rows_flipped: self.columns_flipped,
+            columns_flipped: self.rows_flipped,
+        }
+    }
+
+    pub fn flip_vertical(self) -> Self {
+        Orientation {
+            rows_flipped: !self.rows_flipped,
+            ..self
+        }
+    }
+
+    pub fn flip_horizontal(self) -> Self {
+        Orientation {
+            columns_flipped: !self.columns_flipped,
+            ..self
+        }
+    }
+
+    pub fn rotate_clockwise(self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    pub fn rotate_anticlockwise(self) -> Self {
+        self.transpose().flip_vertical()
+    }
+
+    pub fn rotate_180(self) -> Self {
+        self.flip_vertical().flip_horizontal()
+    }
+
+    pub fn dimensions(&self, base: &Position) -> Position {
+        if self.transposed {
+            Position {
+                row: base.column,
+                column: base.row,
+            }
+        } else {
+            *base
+        }
+    }
+
+    /// Maps a position in the oriented view onto the underlying grid, or
+    /// `None` if it lies outside of the view.
+    pub fn to_base(&self, position: &Position, base: &Position) -> Option<Position> {
+        let dimensions = self.dimensions(base);
+        if position.row >= dimensions.row || position.column >= dimensions.column {
+            return None;
+        }
+
+        let row = if self.rows_flipped {
+            dimensions.row - 1 - position.row
+        } else {
+            position.row
+        };
+        let column = if self.columns_flipped {
+            dimensions.column - 1 - position.column
+        } else {
+            position.column
+        };
+
+        if self.transposed {
+            Some(Position {
+                row: column,
+                column: row,
+            })
+        } else {
+            Some(Position { row, column })
+        }
+    }
+}
+
+/// A read-only view of a grid under some orientation. No cells are copied.
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    orientation: Orientation,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn new(grid: &'a Grid<T>, orientation: Orientation) -> Self {
+        GridView { grid, orientation }
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    pub fn transpose(self) -> Self {
+        Self::new(self.grid, self.orientation.transpose())
+    }
+
+    pub fn rotate_clockwise(self) -> Self {
+        Self::new(self.grid, self.orientation.rotate_clockwise())
+    }
+
+    pub fn rotate_anticlockwise(self) -> Self {
+        Self::new(self.grid, self.orientation.rotate_anticlockwise())
+    }
+
+    pub fn rotate_180(self) -> Self {
+        Self::new(self.grid, self.orientation.rotate_180())
+    }
+
+    pub fn flip_horizontal(self) -> Self {
+        Self::new(self.grid, self.orientation.flip_horizontal())
+    }
+
+    pub fn flip_vertical(self) -> Self {
+        Self::new(self.grid, self.orientation.flip_vertical())
+    }
+
+    pub fn dimensions(&self) -> Position {
+        self.orientation.dimensions(&self.grid.dimensions())
+    }
+
+    pub fn get(&self, position: &Position<usize>) -> Option<&'a T> {
+        let base = self
+            .orientation
+            .to_base(position, &self.grid.dimensions())?;
+        self.grid.get(&base)
+    }
+
+    pub fn row(&self, row: usize) -> impl Iterator<Item = &'a T> + use<'a, T> {
+        let view = *self;
+        (0..self.dimensions().column).filter_map(move |column| view.get(&Position { row, column }))
+    }
+
+    pub fn rows(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = &'a T> + use<'a, T>> + use<'a, T> {
+        let view = *self;
+        (0..self.dimensions().row).map(move |row| view.row(row))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &'a T)> + use<'a, T> {
+        let view = *self;
+        let dimensions = self.dimensions();
+        (0..dimensions.row).flat_map(move |row| {
+            (0..dimensions.column).filter_map(move |column| {
+                let position = Position { row, column };
+                let value = view.get(&position)?;
+                Some((position, value))
+            })
+        })
+    }
+
+    /// Copies the view into a new grid with its current orientation.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(self.rows().map(|row| row.cloned().collect()).collect())
+    }
+}
+
+/// A mutable view of a grid under some orientation. Writes go straight
+/// through to the underlying grid.
+pub struct GridViewMut<'a, T> {
+    grid: &'a mut Grid<T>,
+    orientation: Orientation,
+}
+
+impl<'a, T> GridViewMut<'a, T> {
+    pub fn new(grid: &'a mut Grid<T>, orientation: Orientation) -> Self {
+        GridViewMut { grid, orientation }
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView::new(self.grid, self.orientation)
+    }
+
+    pub fn transpose(self) -> Self {
+        Self::new(self.grid, self.orientation.transpose())
+    }
+
+    pub fn rotate_clockwise(self) -> Self {
+        Self::new(self.grid, self.orientation.rotate_clockwise())
+    }
+
+    pub fn rotate_anticlockwise(self) -> Self {
+        Self::new(self.grid, self.orientation.rotate_anticlockwise())
+    }
+
+    pub fn rotate_180(self) -> Self {
+        Self::new(self.grid, self.orientation.rotate_180())
+    }
+
+    pub fn flip_horizontal(self) -> Self {
+        Self::new(self.grid, self.orientation.flip_horizontal())
+    }
+
+    pub fn flip_vertical(self) -> Self {
+        Self::new(self.grid, self.orientation.flip_vertical())
+    }
+
+    pub fn dimensions(&self) -> Position {
+        self.orientation.dimensions(&self.grid.dimensions())
+    }
+
+    pub fn get(&self, position: &Position<usize>) -> Option<&T> {
+        let base = self
+            .orientation
+            .to_base(position, &self.grid.dimensions())?;
+        self.grid.get(&base)
+    }
+
+    pub fn get_mut(&mut self, position: &Position<usize>) -> Option<&mut T> {
+        let base = self
+            .orientation
+            .to_base(position, &self.grid.dimensions())?;
+        self.grid.get_mut(&base)
+    }
+
+    pub fn set(&mut self, position: &Position<usize>, value: T) {
+        if let Some(base) = self.orientation.to_base(position, &self.grid.dimensions()) {
+            self.grid.set(&base, value);
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn view(&self) -> GridView<'_, T> {
+        GridView::new(self, Orientation::default())
+    }
+
+    pub fn view_mut(&mut self) -> GridViewMut<'_, T> {
+        GridViewMut::new(self, Orientation::default())
+    }
+
+    pub fn transpose(&self) -> GridView<'_, T> {
+        self.view().transpose()
+    }
+
+    pub fn rotate_clockwise(&self) -> GridView<'_, T> {
+        self.view().rotate_clockwise()
+    }
+
+    pub fn rotate_anticlockwise(&self) -> GridView<'_, T> {
+        self.view().rotate_anticlockwise()
+    }
+
+    pub fn rotate_180(&self) -> GridView<'_, T> {
+        self.view().rotate_180()
+    }
+
+    pub fn flip_horizontal(&self) -> GridView<'_, T> {
+        self.view().flip_horizontal()
+    }
+
+    pub fn flip_vertical(&self) -> GridView<'_, T> {
+        self.view().flip_vertical()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows_of(view: GridView<'_, i32>) -> Vec<Vec<i32>> {
+        view.rows().map(|row| row.copied().collect()).collect()
+    }
+
+    #[test]
+    fn it_transposes() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let view = grid.transpose();
+
+        assert_eq!(view.dimensions(), Position { row: 3, column: 2 });
+        assert_eq!(rows_of(view), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+    }
+
+    #[test]
+    fn it_rotates() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        assert_eq!(
+            rows_of(grid.rotate_clockwise()),
+            vec![vec![4, 1], vec![5, 2], vec![6, 3]]
+        );
+        assert_eq!(
+            rows_of(grid.rotate_anticlockwise()),
+            vec![vec![3, 6], vec![2, 5], vec![1, 4]]
+        );
+        assert_eq!(
+            rows_of(grid.rotate_180()),
+            vec![vec![6, 5, 4], vec![3, 2, 1]]
+        );
+    }
+
+    #[test]
+    fn it_flips() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        assert_eq!(
+            rows_of(grid.flip_horizontal()),
+            vec![vec![3, 2, 1], vec![6, 5, 4]]
+        );
+        assert_eq!(
+            rows_of(grid.flip_vertical()),
+            vec![vec![4, 5, 6], vec![1, 2, 3]]
+        );
+    }
+
+    #[test]
+    fn it_composes_transforms() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        let full_turn = grid
+            .rotate_clockwise()
+            .rotate_clockwise()
+            .rotate_clockwise()
+            .rotate_clockwise();
+        assert_eq!(full_turn.orientation(), Orientation::default());
+
+        let half_turn = grid.rotate_clockwise().rotate_clockwise();
+        assert_eq!(half_turn.orientation(), grid.rotate_180().orientation());
+
+        let undone = grid.rotate_clockwise().rotate_anticlockwise();
+        assert_eq!(rows_of(undone), vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    }
+
+    #[test]
+    fn it_lists_every_orientation() {
+        let rotations: Vec<Orientation> = (0..4)
+            .scan(Orientation::IDENTITY, |orientation, _| {
+                let current = *orientation;
+                *orientation = orientation.rotate_clockwise();
+                Some(current)
+            })
+            .collect();
+        assert_eq!(rotations, Orientation::ROTATIONS);
+
+        let mirrored: Vec<Orientation> = rotations
+            .iter()
+            .map(|orientation| orientation.flip_horizontal())
+            .collect();
+        assert_eq!(mirrored, Orientation::ALL[4..]);
+    }
+
+    #[test]
+    fn it_writes_through_a_mutable_view() {
+        let mut grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        let mut view = grid.view_mut().rotate_clockwise();
+        view.set(&Position { row: 0, column: 0 }, 40);
+        *view.get_mut(&Position { row: 2, column: 1 }).unwrap() = 30;
+
+        assert_eq!(grid.as_slice(), &[1, 2, 30, 40, 5, 6]);
+    }
+
+    #[test]
+    fn it_copies_a_view_into_a_grid() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let transposed = grid.transpose().to_grid();
+
+        assert_eq!(transposed.dimensions(), Position { row: 3, column: 2 });
+        assert_eq!(transposed.as_slice(), &[1, 4, 2, 5, 3, 6]);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::direction::Direction;
+use crate::grid::Grid;
+use crate::position::Position;
+
+/// A view which repeats a grid infinitely in every direction. Positions are
+/// signed, with the original grid occupying tile `(0, 0)`.
+pub struct WrappedGrid<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<T> Clone for WrappedGrid<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for WrappedGrid<'_, T> {}
+
+impl<'a, T> WrappedGrid<'a, T> {
+    pub fn new(grid: &'a Grid<T>) -> Self {
+        WrappedGrid { grid }
+    }
+
+    /// Splits a position into the matching cell of the underlying grid and
+    /// the tile copy which it falls in. Returns `None` for an empty grid.
+    pub fn wrap(&self, position: &Position<i64>) -> Option<(Position, Position<i64>)> {
+        let dimensions = self.grid.dimensions();
+        let height = i64::try_from(dimensions.row).ok().filter(|&h| h > 0)?;
+        let width = i64::try_from(dimensions.column).ok().filter(|&w| w > 0)?;
+
+        let base = Position {
+            row: position.row.rem_euclid(height) as usize,
+            column: position.column.rem_euclid(width) as usize,
+        };
+        let tile = Position {
+            row: position.row.div_euclid(height),
+            column: position.column.div_euclid(width),
+        };
+        Some((base, tile))
+    }
+
+    pub fn base_position(&self, position: &Position<i64>) -> Option<Position> {
+        self.wrap(position).map(|(base, _)| base)
+    }
+
+    pub fn tile(&self, position: &Position<i64>) -> Option<Position<i64>> {
+        self.wrap(position).map(|(_, tile)| tile)
+    }
+
+    pub fn get(&self, position: &Position<i64>) -> Option<&'a T> {
+        self.grid.get(&self.base_position(position)?)
+    }
+
+    pub fn neighbours_with_direction(
+        &self,
+        position: &Position<i64>,
+    ) -> impl Iterator<Item = (Direction, Position<i64>, &'a T)> + use<'a, T> {
+        let view = *self;
+        let position = *position;
+        Direction::ALL.into_iter().filter_map(move |direction| {
+            let neighbour = direction.travel(&position);
+            let value = view.get(&neighbour)?;
+            Some((direction, neighbour, value))
+        })
+    }
+
+    pub fn neighbours(
+        &self,
+        position: &Position<i64>,
+    ) -> impl Iterator<Item = (Position<i64>, &'a T)> + use<'a, T> {
+        self.neighbours_with_direction(position)
+            .map(|(_, neighbour, value)| (neighbour, value))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn wrapped(&self) -> WrappedGrid<'_, T> {
+        WrappedGrid::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_wraps_positions() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let wrapped = grid.wrapped();
+
+        assert_eq!(
+            wrapped.wrap(&Position { row: 1, column: 2 }),
+            Some((
+                Position { row: 1, column: 2 },
+                Position { row: 0, column: 0 }
+            ))
+        );
+        assert_eq!(
+            wrapped.wrap(&Position { row: -1, column: 7 }),
+            Some((
+                Position { row: 1, column: 1 },
+                Position { row: -1, column: 2 }
+            ))
+        );
+        assert_eq!(
+            wrapped.wrap(&Position {
+                row: -2,
+                column: -3
+            }),
+            Some((
+                Position { row: 0, column: 0 },
+                Position {
+                    row: -1,
+                    column: -1
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn it_reads_through_the_wrap() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let wrapped = grid.wrapped();
+
+        assert_eq!(
+            wrapped.get(&Position {
+                row: -1,
+                column: -1
+            }),
+            Some(&6)
+        );
+        assert_eq!(wrapped.get(&Position { row: 4, column: 4 }), Some(&2));
+
+        let neighbours: Vec<&i32> = wrapped
+            .neighbours(&Position { row: 0, column: 0 })
+            .map(|(_, value)| value)
+            .collect();
+        assert_eq!(neighbours, vec![&4, &2, &4, &3]);
+    }
+
+    #[test]
+    fn it_handles_an_empty_grid() {
+        let grid: Grid<i32> = Grid::new(vec![]);
+        assert_eq!(grid.wrapped().get(&Position { row: 0, column: 0 }), None);
+    }
+}
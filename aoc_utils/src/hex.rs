@@ -0,0 +1,492 @@
+use crate::grid::Grid;
+use crate::grid::sparse::SparseGrid;
+use crate::position::Position;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+/// A step between neighbouring hexagons. Both hexagon layouts share the same
+/// axial coordinates, so `Hex` can travel in the directions of either.
+pub trait HexStep {
+    /// The change in axial coordinates from taking one step.
+    fn offset(&self) -> Hex;
+}
+
+/// The six directions between neighbouring hexagons, for hexagons with flat
+/// tops and bottoms.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum HexDirection {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+impl HexDirection {
+    /// Every direction, clockwise from north.
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::North,
+        HexDirection::NorthEast,
+        HexDirection::SouthEast,
+        HexDirection::South,
+        HexDirection::SouthWest,
+        HexDirection::NorthWest,
+    ];
+
+    /// The change in axial coordinates from taking one step.
+    pub fn offset(&self) -> Hex {
+        let (q, r) = match self {
+            HexDirection::North => (0, -1),
+            HexDirection::NorthEast => (1, -1),
+            HexDirection::SouthEast => (1, 0),
+            HexDirection::South => (0, 1),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::NorthWest => (-1, 0),
+        };
+        Hex { q, r }
+    }
+
+    pub fn turn_clockwise(&self) -> HexDirection {
+        let index = HexDirection::ALL.iter().position(|d| d == self).unwrap();
+        HexDirection::ALL[(index + 1) % 6]
+    }
+
+    pub fn turn_anticlockwise(&self) -> HexDirection {
+        let index = HexDirection::ALL.iter().position(|d| d == self).unwrap();
+        HexDirection::ALL[(index + 5) % 6]
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ParseHexDirectionError {
+    pub direction: String,
+}
+
+impl fmt::Display for ParseHexDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is not a valid hex direction", self.direction)
+    }
+}
+
+impl Error for ParseHexDirectionError {}
+
+impl HexStep for HexDirection {
+    fn offset(&self) -> Hex {
+        HexDirection::offset(self)
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = ParseHexDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "n" => Ok(HexDirection::North),
+            "ne" => Ok(HexDirection::NorthEast),
+            "se" => Ok(HexDirection::SouthEast),
+            "s" => Ok(HexDirection::South),
+            "sw" => Ok(HexDirection::SouthWest),
+            "nw" => Ok(HexDirection::NorthWest),
+            _ => Err(ParseHexDirectionError {
+                direction: s.to_string(),
+            }),
+        }
+    }
+}
+
+/// Parses a comma separated list of directions such as `"ne,ne,s,nw"`.
+pub fn parse_directions(input: &str) -> Result<Vec<HexDirection>, ParseHexDirectionError> {
+    input
+        .trim()
+        .split(',')
+        .filter(|direction| !direction.trim().is_empty())
+        .map(|direction| direction.trim().parse())
+        .collect()
+}
+
+/// The six directions between neighbouring hexagons, for hexagons with
+/// pointed tops and bottoms, where each row sits half a hexagon across from
+/// the one above it.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum PointyHexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl PointyHexDirection {
+    /// Every direction, clockwise from east.
+    pub const ALL: [PointyHexDirection; 6] = [
+        PointyHexDirection::East,
+        PointyHexDirection::SouthEast,
+        PointyHexDirection::SouthWest,
+        PointyHexDirection::West,
+        PointyHexDirection::NorthWest,
+        PointyHexDirection::NorthEast,
+    ];
+
+    /// The change in axial coordinates from taking one step.
+    pub fn offset(&self) -> Hex {
+        let (q, r) = match self {
+            PointyHexDirection::East => (1, 0),
+            PointyHexDirection::SouthEast => (0, 1),
+            PointyHexDirection::SouthWest => (-1, 1),
+            PointyHexDirection::West => (-1, 0),
+            PointyHexDirection::NorthWest => (0, -1),
+            PointyHexDirection::NorthEast => (1, -1),
+        };
+        Hex { q, r }
+    }
+
+    pub fn turn_clockwise(&self) -> PointyHexDirection {
+        let index = PointyHexDirection::ALL
+            .iter()
+            .position(|d| d == self)
+            .unwrap();
+        PointyHexDirection::ALL[(index + 1) % 6]
+    }
+
+    pub fn turn_anticlockwise(&self) -> PointyHexDirection {
+        let index = PointyHexDirection::ALL
+            .iter()
+            .position(|d| d == self)
+            .unwrap();
+        PointyHexDirection::ALL[(index + 5) % 6]
+    }
+}
+
+impl HexStep for PointyHexDirection {
+    fn offset(&self) -> Hex {
+        PointyHexDirection::offset(self)
+    }
+}
+
+impl FromStr for PointyHexDirection {
+    type Err = ParseHexDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "e" => Ok(PointyHexDirection::East),
+            "se" => Ok(PointyHexDirection::SouthEast),
+            "sw" => Ok(PointyHexDirection::SouthWest),
+            "w" => Ok(PointyHexDirection::West),
+            "nw" => Ok(PointyHexDirection::NorthWest),
+            "ne" => Ok(PointyHexDirection::NorthEast),
+            _ => Err(ParseHexDirectionError {
+                direction: s.to_string(),
+            }),
+        }
+    }
+}
+
+/// Parses directions written one after another with nothing between them,
+/// such as `"esenee"`. A north or south is always paired with the east or
+/// west which follows it.
+pub fn parse_pointy_directions(
+    input: &str,
+) -> Result<Vec<PointyHexDirection>, ParseHexDirectionError> {
+    let mut characters = input.trim().chars();
+    let mut directions = vec![];
+    while let Some(first) = characters.next() {
+        let mut direction = first.to_string();
+        if matches!(first, 'n' | 's')
+            && let Some(second) = characters.next()
+        {
+            direction.push(second);
+        }
+        directions.push(direction.parse()?);
+    }
+    Ok(directions)
+}
+
+/// A hexagon in axial coordinates. The third cube coordinate is implied by
+/// `q + r + s = 0`.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+impl Hex {
+    pub const ORIGIN: Hex = Hex { q: 0, r: 0 };
+
+    pub fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    /// The cube coordinates `(q, r, s)` of the hexagon.
+    pub fn cube(&self) -> (i64, i64, i64) {
+        (self.q, self.r, self.s())
+    }
+
+    pub fn travel<D: HexStep>(&self, direction: &D) -> Hex {
+        *self + direction.offset()
+    }
+
+    pub fn travel_n<D: HexStep>(&self, direction: &D, n: i64) -> Hex {
+        let offset = direction.offset();
+        Hex {
+            q: self.q + offset.q * n,
+            r: self.r + offset.r * n,
+        }
+    }
+
+    pub fn neighbours(&self) -> impl Iterator<Item = (HexDirection, Hex)> + use<> {
+        let hex = *self;
+        HexDirection::ALL
+            .into_iter()
+            .map(move |direction| (direction, hex.travel(&direction)))
+    }
+
+    /// The number of steps between two hexagons.
+    pub fn distance(a: &Hex, b: &Hex) -> i64 {
+        let difference = *a - *b;
+        (difference.q.abs() + difference.r.abs() + difference.s().abs()) / 2
+    }
+
+    /// Every hexagon at exactly `radius` steps away, in clockwise order. The
+    /// ring of radius zero is just the centre, and there are no hexagons at a
+    /// negative radius.
+    pub fn ring(&self, radius: i64) -> Vec<Hex> {
+        match radius {
+            ..0 => return Vec::new(),
+            0 => return vec![*self],
+            _ => {}
+        }
+
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        let mut hex = self.travel_n(&HexDirection::NorthWest, radius);
+        for direction in [
+            HexDirection::NorthEast,
+            HexDirection::SouthEast,
+            HexDirection::South,
+            HexDirection::SouthWest,
+            HexDirection::NorthWest,
+            HexDirection::North,
+        ] {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex = hex.travel(&direction);
+            }
+        }
+        ring
+    }
+
+    /// Every hexagon within `radius` steps, starting from the centre and
+    /// working outwards one ring at a time.
+    pub fn spiral(&self, radius: i64) -> Vec<Hex> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+
+    /// The position of the hexagon when laid out in rows and columns.
+    pub fn to_offset(&self, layout: HexLayout) -> Position<i64> {
+        match layout {
+            HexLayout::FlatTop => Position {
+                row: self.r + (self.q - (self.q & 1)) / 2,
+                column: self.q,
+            },
+            HexLayout::PointyTop => Position {
+                row: self.r,
+                column: self.q + (self.r - (self.r & 1)) / 2,
+            },
+        }
+    }
+
+    pub fn from_offset(position: &Position<i64>, layout: HexLayout) -> Hex {
+        let Position { row, column } = *position;
+        match layout {
+            HexLayout::FlatTop => Hex {
+                q: column,
+                r: row - (column - (column & 1)) / 2,
+            },
+            HexLayout::PointyTop => Hex {
+                q: column - (row - (row & 1)) / 2,
+                r: row,
+            },
+        }
+    }
+}
+
+/// How hexagons are laid out in rows and columns by their offset coordinates.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum HexLayout {
+    /// Flat topped hexagons in columns, with every odd column pushed half a
+    /// row down.
+    FlatTop,
+    /// Pointy topped hexagons in rows, with every odd row pushed half a
+    /// column right.
+    PointyTop,
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex {
+            q: self.q + other.q,
+            r: self.r + other.r,
+        }
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, other: Hex) -> Hex {
+        Hex {
+            q: self.q - other.q,
+            r: self.r - other.r,
+        }
+    }
+}
+
+/// Lays hexagons out in a grid using their offset coordinates in the given
+/// layout, so they can be printed. Returns the grid along with the offset
+/// position of its top left cell.
+pub fn to_grid<I, T>(cells: I, default: T, layout: HexLayout) -> (Grid<T>, Position<i64>)
+where
+    I: IntoIterator<Item = (Hex, T)>,
+    T: Clone,
+{
+    let mut grid = SparseGrid::new(default);
+    grid.extend(
+        cells
+            .into_iter()
+            .map(|(hex, value)| (hex.to_offset(layout), value)),
+    );
+    grid.to_grid()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walk(path: &str) -> (Hex, i64) {
+        let directions = parse_directions(path).unwrap();
+        let mut hex = Hex::ORIGIN;
+        let mut furthest = 0;
+        for direction in directions {
+            hex = hex.travel(&direction);
+            furthest = furthest.max(Hex::distance(&hex, &Hex::ORIGIN));
+        }
+        (hex, furthest)
+    }
+
+    #[test]
+    fn it_measures_distances() {
+        assert_eq!(Hex::distance(&walk("ne,ne,ne").0, &Hex::ORIGIN), 3);
+        assert_eq!(Hex::distance(&walk("ne,ne,sw,sw").0, &Hex::ORIGIN), 0);
+        assert_eq!(Hex::distance(&walk("ne,ne,s,s").0, &Hex::ORIGIN), 2);
+        assert_eq!(Hex::distance(&walk("se,sw,se,sw,sw").0, &Hex::ORIGIN), 3);
+        assert_eq!(walk("ne,ne,sw,sw").1, 2);
+    }
+
+    #[test]
+    fn it_rejects_invalid_directions() {
+        let error = parse_directions("n,e,s").unwrap_err();
+        assert_eq!(error.to_string(), "\"e\" is not a valid hex direction");
+    }
+
+    #[test]
+    fn it_parses_pointy_directions_without_separators() {
+        let walk = |path: &str| {
+            parse_pointy_directions(path)
+                .unwrap()
+                .iter()
+                .fold(Hex::ORIGIN, |hex, direction| hex.travel(direction))
+        };
+
+        assert_eq!(parse_pointy_directions("esenee").unwrap().len(), 4);
+        assert_eq!(
+            walk("esew"),
+            Hex::ORIGIN.travel(&PointyHexDirection::SouthEast)
+        );
+        assert_eq!(walk("nwwswee"), Hex::ORIGIN);
+        assert!(parse_pointy_directions("enw").is_ok());
+        assert_eq!(
+            parse_pointy_directions("ens").unwrap_err().to_string(),
+            "\"ns\" is not a valid hex direction"
+        );
+    }
+
+    #[test]
+    fn it_builds_rings_and_spirals() {
+        let centre = Hex { q: 2, r: -1 };
+
+        assert_eq!(centre.ring(0), vec![centre]);
+        assert_eq!(centre.neighbours().count(), 6);
+
+        let ring = centre.ring(3);
+        assert_eq!(ring.len(), 18);
+        assert!(ring.iter().all(|hex| Hex::distance(hex, &centre) == 3));
+        assert!(
+            ring.windows(2)
+                .all(|pair| Hex::distance(&pair[0], &pair[1]) == 1)
+        );
+
+        assert_eq!(centre.spiral(2).len(), 1 + 6 + 12);
+        assert_eq!(centre.ring(-1), vec![]);
+        assert_eq!(centre.spiral(-1), vec![]);
+    }
+
+    #[test]
+    fn it_converts_to_offset_coordinates() {
+        for layout in [HexLayout::FlatTop, HexLayout::PointyTop] {
+            for hex in Hex::ORIGIN.spiral(3) {
+                assert_eq!(Hex::from_offset(&hex.to_offset(layout), layout), hex);
+            }
+        }
+
+        let cells = Hex::ORIGIN.ring(1).into_iter().map(|hex| (hex, '#'));
+        let (grid, origin) = to_grid(cells, '.', HexLayout::FlatTop);
+        assert_eq!(
+            origin,
+            Position {
+                row: -1,
+                column: -1
+            }
+        );
+        assert_eq!(grid.dimensions(), Position { row: 3, column: 3 });
+        assert_eq!(grid[Position { row: 1, column: 1 }], '.');
+        assert_eq!(grid[Position { row: 0, column: 1 }], '#');
+    }
+
+    #[test]
+    fn it_lays_out_pointy_topped_hexagons() {
+        let east = Hex::ORIGIN.travel(&PointyHexDirection::East);
+        let south_east = Hex::ORIGIN.travel(&PointyHexDirection::SouthEast);
+        let south_west = Hex::ORIGIN.travel(&PointyHexDirection::SouthWest);
+
+        assert_eq!(
+            east.to_offset(HexLayout::PointyTop),
+            Position { row: 0, column: 1 }
+        );
+        assert_eq!(
+            south_east.to_offset(HexLayout::PointyTop),
+            Position { row: 1, column: 0 }
+        );
+        assert_eq!(
+            south_west.to_offset(HexLayout::PointyTop),
+            Position { row: 1, column: -1 }
+        );
+
+        let cells = Hex::ORIGIN.ring(1).into_iter().map(|hex| (hex, '#'));
+        let (grid, origin) = to_grid(cells, '.', HexLayout::PointyTop);
+        assert_eq!(
+            origin,
+            Position {
+                row: -1,
+                column: -1
+            }
+        );
+        assert_eq!(grid.dimensions(), Position { row: 3, column: 3 });
+        assert_eq!(grid[Position { row: 1, column: 1 }], '.');
+        assert_eq!(grid[Position { row: 1, column: 2 }], '#');
+    }
+}
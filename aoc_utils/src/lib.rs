@@ -1,6 +1,12 @@
+pub mod cycle;
 pub mod direction;
 pub mod graph;
 pub mod grid;
+pub mod hex;
 pub mod math;
 pub mod position;
+pub mod rect;
+pub mod render;
 pub mod traits;
+pub mod vector;
+pub mod voxel;
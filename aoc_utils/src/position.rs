@@ -1,4 +1,10 @@
-use std::ops::{Add, Div, Mul, Sub};
+use crate::rect::Rect;
+use crate::traits::{Signed, Zero};
+use crate::vector::Vector;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
 
 pub trait Scalar {}
 impl Scalar for i32 {}
@@ -6,7 +12,7 @@ impl Scalar for f64 {}
 impl Scalar for u16 {}
 impl Scalar for usize {}
 
-#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash, Debug, Default)]
 pub struct Position<T = usize> {
     pub row: T,
     pub column: T,
@@ -21,10 +27,106 @@ impl Position<i16> {
     }
 }
 
+impl Position<usize> {
+    /// Moves by an offset, or `None` if that would leave the boundary. The
+    /// boundary can be a `Rect`, or a `Position` which is treated as the
+    /// rectangle from zero up to it.
+    pub fn checked_offset<B>(&self, offset: &Position<isize>, boundary: &B) -> Option<Position>
+    where
+        B: Copy + Into<Rect>,
+    {
+        let position = Position {
+            row: self.row.checked_add_signed(offset.row)?,
+            column: self.column.checked_add_signed(offset.column)?,
+        };
+        (*boundary).into().contains(&position).then_some(position)
+    }
+
+    /// Moves by an offset, wrapping around to the opposite side of the
+    /// boundary. Only `None` if the boundary is empty.
+    pub fn wrapping_offset<B>(&self, offset: &Position<isize>, boundary: &B) -> Option<Position>
+    where
+        B: Copy + Into<Rect>,
+    {
+        let Rect { min, max } = (*boundary).into();
+        let wrap = |value: usize, offset: isize, min: usize, max: usize| {
+            (min < max).then(|| {
+                let size = (max - min) as i128;
+                let moved = value as i128 - min as i128 + offset as i128;
+                min + moved.rem_euclid(size) as usize
+            })
+        };
+        Some(Position {
+            row: wrap(self.row, offset.row, min.row, max.row)?,
+            column: wrap(self.column, offset.column, min.column, max.column)?,
+        })
+    }
+
+    /// Moves by an offset, stopping at the edge of the boundary. Only `None`
+    /// if the boundary is empty.
+    pub fn saturating_offset<B>(&self, offset: &Position<isize>, boundary: &B) -> Option<Position>
+    where
+        B: Copy + Into<Rect>,
+    {
+        let Rect { min, max } = (*boundary).into();
+        let clamp = |value: usize, offset: isize, min: usize, max: usize| {
+            (min < max).then(|| value.saturating_add_signed(offset).clamp(min, max - 1))
+        };
+        Some(Position {
+            row: clamp(self.row, offset.row, min.row, max.row)?,
+            column: clamp(self.column, offset.column, min.column, max.column)?,
+        })
+    }
+}
+
+/// The error from converting a position to an integer type which cannot
+/// hold one of its coordinates.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct TryFromPositionError;
+
+impl fmt::Display for TryFromPositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "position is out of range for the target type")
+    }
+}
+
+impl Error for TryFromPositionError {}
+
+macro_rules! impl_try_from {
+    ($from:ty; $($to:ty),*) => {
+        $(
+            impl TryFrom<Position<$from>> for Position<$to> {
+                type Error = TryFromPositionError;
+
+                fn try_from(position: Position<$from>) -> Result<Self, Self::Error> {
+                    Ok(Position {
+                        row: <$to>::try_from(position.row).map_err(|_| TryFromPositionError)?,
+                        column: <$to>::try_from(position.column)
+                            .map_err(|_| TryFromPositionError)?,
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from!(i8; i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_try_from!(i16; i8, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_try_from!(i32; i8, i16, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_try_from!(i64; i8, i16, i32, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_try_from!(i128; i8, i16, i32, i64, isize, u8, u16, u32, u64, u128, usize);
+impl_try_from!(isize; i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, usize);
+impl_try_from!(u8; i8, i16, i32, i64, i128, isize, u16, u32, u64, u128, usize);
+impl_try_from!(u16; i8, i16, i32, i64, i128, isize, u8, u32, u64, u128, usize);
+impl_try_from!(u32; i8, i16, i32, i64, i128, isize, u8, u16, u64, u128, usize);
+impl_try_from!(u64; i8, i16, i32, i64, i128, isize, u8, u16, u32, u128, usize);
+impl_try_from!(u128; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+impl_try_from!(usize; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128);
+
 impl<T> Position<T> {
     pub fn manhattan_distance(a: &Position<T>, b: &Position<T>) -> T
     where
-        T: Add<Output = T> + Sub<Output = T> + Copy + Ord,
+        T: Add<Output = T> + Sub<Output = T> + Copy + PartialOrd,
     {
         let row_diff = if a.row > b.row {
             a.row - b.row
@@ -38,88 +140,535 @@ impl<T> Position<T> {
         };
         row_diff + col_diff
     }
+
+    /// The number of steps between two positions when diagonal steps are
+    /// allowed.
+    pub fn chebyshev_distance(a: &Position<T>, b: &Position<T>) -> T
+    where
+        T: Sub<Output = T> + Copy + PartialOrd,
+    {
+        let difference = |a: T, b: T| if a > b { a - b } else { b - a };
+        let row_diff = difference(a.row, b.row);
+        let col_diff = difference(a.column, b.column);
+        if row_diff > col_diff {
+            row_diff
+        } else {
+            col_diff
+        }
+    }
+
+    pub fn squared_euclidean_distance(a: &Position<T>, b: &Position<T>) -> T
+    where
+        T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy + PartialOrd,
+    {
+        let difference = |a: T, b: T| if a > b { a - b } else { b - a };
+        let row_diff = difference(a.row, b.row);
+        let col_diff = difference(a.column, b.column);
+        row_diff * row_diff + col_diff * col_diff
+    }
+
+    pub fn dot(&self, other: &Position<T>) -> T
+    where
+        T: Add<Output = T> + Mul<Output = T> + Copy + Zero,
+    {
+        Vector::from(*self).dot(&Vector::from(*other))
+    }
+
+    /// The 2D cross product, which is positive when `other` is clockwise of
+    /// `self` as displayed with rows running down the screen. Half the sum of
+    /// the cross products around a loop gives its area (the shoelace formula).
+    pub fn cross(&self, other: &Position<T>) -> T
+    where
+        T: Sub<Output = T> + Mul<Output = T> + Copy,
+    {
+        self.column * other.row - self.row * other.column
+    }
+
+    /// Rotates a quarter turn clockwise about the origin, so that a step
+    /// right becomes a step down.
+    pub fn rotate_clockwise(&self) -> Position<T>
+    where
+        T: Neg<Output = T> + Copy,
+    {
+        Position {
+            row: self.column,
+            column: -self.row,
+        }
+    }
+
+    /// Rotates a quarter turn anticlockwise about the origin, so that a step
+    /// right becomes a step up.
+    pub fn rotate_anticlockwise(&self) -> Position<T>
+    where
+        T: Neg<Output = T> + Copy,
+    {
+        Position {
+            row: -self.column,
+            column: self.row,
+        }
+    }
+
+    pub fn rotate_clockwise_about(&self, pivot: &Position<T>) -> Position<T>
+    where
+        T: Add<Output = T> + Sub<Output = T> + Neg<Output = T> + Copy,
+    {
+        (self - pivot).rotate_clockwise() + pivot
+    }
+
+    pub fn rotate_anticlockwise_about(&self, pivot: &Position<T>) -> Position<T>
+    where
+        T: Add<Output = T> + Sub<Output = T> + Neg<Output = T> + Copy,
+    {
+        (self - pivot).rotate_anticlockwise() + pivot
+    }
+
+    /// Reflects across the horizontal axis, negating the row.
+    pub fn flip_vertical(&self) -> Position<T>
+    where
+        T: Neg<Output = T> + Copy,
+    {
+        Position {
+            row: -self.row,
+            column: self.column,
+        }
+    }
+
+    /// Reflects across the vertical axis, negating the column.
+    pub fn flip_horizontal(&self) -> Position<T>
+    where
+        T: Neg<Output = T> + Copy,
+    {
+        Position {
+            row: self.row,
+            column: -self.column,
+        }
+    }
+
+    /// The point on the opposite side of the pivot at the same distance.
+    pub fn reflect_about(&self, pivot: &Position<T>) -> Position<T>
+    where
+        T: Add<Output = T> + Sub<Output = T> + Copy,
+    {
+        pivot + pivot - self
+    }
+
+    pub fn abs(&self) -> Position<T>
+    where
+        T: Signed + Copy,
+    {
+        Vector::from(*self).abs().into()
+    }
+
+    pub fn signum(&self) -> Position<T>
+    where
+        T: Signed + Copy,
+    {
+        Vector::from(*self).signum().into()
+    }
+
+    /// The smallest row and the smallest column of the two positions.
+    pub fn component_min(&self, other: &Position<T>) -> Position<T>
+    where
+        T: PartialOrd + Copy,
+    {
+        Vector::from(*self)
+            .component_min(&Vector::from(*other))
+            .into()
+    }
+
+    /// The largest row and the largest column of the two positions.
+    pub fn component_max(&self, other: &Position<T>) -> Position<T>
+    where
+        T: PartialOrd + Copy,
+    {
+        Vector::from(*self)
+            .component_max(&Vector::from(*other))
+            .into()
+    }
 }
 
-macro_rules! impl_ops {
-    ($trait:ident, $fn:ident, $op:tt) => {
-        // Position <op> Position
-        impl<T: $trait<Output = T> + Copy> $trait for Position<T> {
-            type Output = Position<T>;
-            fn $fn(self, other: Position<T>) -> Position<T> {
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum Metric {
+    /// The number of orthogonal steps between two positions.
+    Manhattan,
+    /// The number of steps between two positions when diagonal steps are
+    /// allowed.
+    Chebyshev,
+}
+
+impl Metric {
+    pub fn distance(&self, a: &Position<i64>, b: &Position<i64>) -> i64 {
+        match self {
+            Metric::Manhattan => Position::manhattan_distance(a, b),
+            Metric::Chebyshev => Position::chebyshev_distance(a, b),
+        }
+    }
+}
+
+/// The positions within a radius of a centre, row by row, along with their
+/// distance from it.
+#[derive(Clone, Debug)]
+pub struct Ball {
+    centre: Position<i64>,
+    radius: i64,
+    metric: Metric,
+    /// Whether to only include positions at exactly the radius.
+    exact: bool,
+    /// The smallest and largest positions which may be included.
+    clip: (Position<i64>, Position<i64>),
+    row: i64,
+    row_end: i64,
+    column: i64,
+    column_end: i64,
+    column_step: i64,
+}
+
+impl Ball {
+    fn new(centre: &Position<i64>, radius: i64, metric: Metric, exact: bool) -> Self {
+        let mut ball = Ball {
+            centre: *centre,
+            radius,
+            metric,
+            exact,
+            clip: (
+                Position {
+                    row: i64::MIN,
+                    column: i64::MIN,
+                },
                 Position {
-                    row: self.row $op other.row,
-                    column: self.column $op other.column,
+                    row: i64::MAX,
+                    column: i64::MAX,
+                },
+            ),
+            row: 0,
+            row_end: 0,
+            column: 0,
+            column_end: 0,
+            column_step: 1,
+        };
+        ball.restart();
+        ball
+    }
+
+    /// Only includes positions which are inside a grid of the given size.
+    pub fn clip(mut self, boundary: &Position) -> Self {
+        self.clip = (
+            Position { row: 0, column: 0 },
+            Position {
+                row: boundary.row as i64 - 1,
+                column: boundary.column as i64 - 1,
+            },
+        );
+        self.restart();
+        self
+    }
+
+    fn restart(&mut self) {
+        self.row = (self.centre.row - self.radius).max(self.clip.0.row);
+        self.row_end = (self.centre.row + self.radius).min(self.clip.1.row);
+        self.start_row();
+    }
+
+    /// Finds which columns of the current row are included. When only the
+    /// positions at exactly the radius are wanted, most rows have just two
+    /// of them, so the step jumps straight from one to the other.
+    fn start_row(&mut self) {
+        let row_distance = (self.row - self.centre.row).abs();
+        let half_width = match self.metric {
+            Metric::Manhattan => self.radius - row_distance,
+            Metric::Chebyshev => self.radius,
+        };
+        let only_ends = self.exact
+            && match self.metric {
+                Metric::Manhattan => true,
+                Metric::Chebyshev => row_distance != self.radius,
+            };
+
+        self.column = self.centre.column - half_width;
+        self.column_end = self.centre.column + half_width;
+        if only_ends {
+            self.column_step = (2 * half_width).max(1);
+        } else {
+            self.column_step = 1;
+            self.column = self.column.max(self.clip.0.column);
+            self.column_end = self.column_end.min(self.clip.1.column);
+        }
+    }
+}
+
+impl Iterator for Ball {
+    type Item = (Position<i64>, i64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.row <= self.row_end {
+            while self.column <= self.column_end {
+                let column = self.column;
+                self.column += self.column_step;
+                if column < self.clip.0.column || column > self.clip.1.column {
+                    continue;
                 }
+                let position = Position {
+                    row: self.row,
+                    column,
+                };
+                let distance = self.metric.distance(&self.centre, &position);
+                return Some((position, distance));
             }
+            self.row += 1;
+            self.start_row();
         }
+        None
+    }
+}
 
-        impl<'a, T: $trait<Output = T> + Copy> $trait<&'a Position<T>> for Position<T> {
-            type Output = Position<T>;
-            fn $fn(self, other: &'a Position<T>) -> Position<T> {
-                Position {
-                    row: self.row $op other.row,
-                    column: self.column $op other.column,
-                }
+impl Position<i64> {
+    /// Every position at most `radius` away under the metric.
+    pub fn ball(&self, radius: i64, metric: Metric) -> Ball {
+        Ball::new(self, radius, metric, false)
+    }
+
+    /// Every position exactly `radius` away under the metric.
+    pub fn ring(&self, radius: i64, metric: Metric) -> Ball {
+        Ball::new(self, radius, metric, true)
+    }
+}
+
+impl Position<usize> {
+    /// Every position of a grid which is at most `radius` away under the
+    /// metric.
+    pub fn ball_within(
+        &self,
+        radius: usize,
+        metric: Metric,
+        boundary: &Position,
+    ) -> impl Iterator<Item = (Position, usize)> + use<> {
+        to_signed(self)
+            .ball(radius as i64, metric)
+            .clip(boundary)
+            .map(to_unsigned)
+    }
+
+    /// Every position of a grid which is exactly `radius` away under the
+    /// metric.
+    pub fn ring_within(
+        &self,
+        radius: usize,
+        metric: Metric,
+        boundary: &Position,
+    ) -> impl Iterator<Item = (Position, usize)> + use<> {
+        to_signed(self)
+            .ring(radius as i64, metric)
+            .clip(boundary)
+            .map(to_unsigned)
+    }
+}
+
+fn to_signed(position: &Position) -> Position<i64> {
+    Position {
+        row: position.row as i64,
+        column: position.column as i64,
+    }
+}
+
+fn to_unsigned((position, distance): (Position<i64>, i64)) -> (Position, usize) {
+    let position = Position {
+        row: position.row as usize,
+        column: position.column as usize,
+    };
+    (position, distance as usize)
+}
+
+/// The order in which the two coordinates of a position are written.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub enum Axes {
+    /// `"row,column"`, which is how positions are displayed.
+    #[default]
+    RowColumn,
+    /// `"x,y"`, where `x` is the column and `y` is the row, as used by most
+    /// puzzle inputs.
+    XY,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ParsePositionError<E> {
+    /// The text did not contain two coordinates separated by a comma.
+    MissingComma,
+    /// One of the coordinates could not be parsed.
+    InvalidCoordinate(E),
+}
+
+impl<E: fmt::Display> fmt::Display for ParsePositionError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParsePositionError::MissingComma => {
+                write!(f, "a position must be two coordinates separated by a comma")
+            }
+            ParsePositionError::InvalidCoordinate(error) => {
+                write!(f, "invalid coordinate: {}", error)
             }
         }
+    }
+}
 
-        impl<'b, T: $trait<Output = T> + Copy> $trait<Position<T>> for &'b Position<T> {
-            type Output = Position<T>;
-            fn $fn(self, other: Position<T>) -> Position<T> {
-                Position {
-                    row: self.row $op other.row,
-                    column: self.column $op other.column,
-                }
+impl<E: fmt::Debug + fmt::Display> Error for ParsePositionError<E> {}
+
+impl<T: FromStr> Position<T> {
+    /// Parses two comma separated coordinates, such as `"3,4"` or
+    /// `"(3, 4)"`, in the given order.
+    pub fn parse(s: &str, axes: Axes) -> Result<Self, ParsePositionError<T::Err>> {
+        let s = s.trim();
+        let s = s
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .unwrap_or(s);
+        let (first, second) = s.split_once(',').ok_or(ParsePositionError::MissingComma)?;
+        let first = first
+            .trim()
+            .parse()
+            .map_err(ParsePositionError::InvalidCoordinate)?;
+        let second = second
+            .trim()
+            .parse()
+            .map_err(ParsePositionError::InvalidCoordinate)?;
+
+        Ok(match axes {
+            Axes::RowColumn => Position {
+                row: first,
+                column: second,
+            },
+            Axes::XY => Position {
+                row: second,
+                column: first,
+            },
+        })
+    }
+}
+
+/// Parses `"row,column"`. Use `Position::parse` with `Axes::XY` for `"x,y"`.
+impl<T: FromStr> FromStr for Position<T> {
+    type Err = ParsePositionError<T::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Position::parse(s, Axes::RowColumn)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Position<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.row, self.column)
+    }
+}
+
+/// The row comes first and the column second.
+impl<T> From<(T, T)> for Position<T> {
+    fn from((row, column): (T, T)) -> Self {
+        Position { row, column }
+    }
+}
+
+impl<T> From<Position<T>> for (T, T) {
+    fn from(position: Position<T>) -> Self {
+        (position.row, position.column)
+    }
+}
+
+impl<T> From<[T; 2]> for Position<T> {
+    fn from([row, column]: [T; 2]) -> Self {
+        Position { row, column }
+    }
+}
+
+impl<T> From<Position<T>> for [T; 2] {
+    fn from(position: Position<T>) -> Self {
+        [position.row, position.column]
+    }
+}
+
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash, Debug, Default)]
+pub struct Position3<T = usize> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Position3<T> {
+    pub fn manhattan_distance(a: &Position3<T>, b: &Position3<T>) -> T
+    where
+        T: Add<Output = T> + Sub<Output = T> + Copy + PartialOrd,
+    {
+        let difference = |a: T, b: T| if a > b { a - b } else { b - a };
+        difference(a.x, b.x) + difference(a.y, b.y) + difference(a.z, b.z)
+    }
+}
+
+// The arithmetic is done by `Vector`, which `Position` and `Position3` are the
+// two and three component versions of. They stay structs with named fields so
+// that the many existing `Position { row, column }` expressions keep working.
+macro_rules! impl_ops {
+    ($type:ident, $trait:ident, $fn:ident, $op:tt) => {
+        // Position <op> Position
+        impl<T: $trait<Output = T> + Copy> $trait for $type<T> {
+            type Output = $type<T>;
+            fn $fn(self, other: $type<T>) -> $type<T> {
+                (Vector::from(self) $op Vector::from(other)).into()
             }
         }
 
-        impl<'a, 'b, T: $trait<Output = T> + Copy> $trait<&'b Position<T>> for &'a Position<T> {
-            type Output = Position<T>;
-            fn $fn(self, other: &'b Position<T>) -> Position<T> {
-                Position {
-                    row: self.row $op other.row,
-                    column: self.column $op other.column,
-                }
+        impl<'a, T: $trait<Output = T> + Copy> $trait<&'a $type<T>> for $type<T> {
+            type Output = $type<T>;
+            fn $fn(self, other: &'a $type<T>) -> $type<T> {
+                (Vector::from(self) $op Vector::from(*other)).into()
+            }
+        }
+
+        impl<'b, T: $trait<Output = T> + Copy> $trait<$type<T>> for &'b $type<T> {
+            type Output = $type<T>;
+            fn $fn(self, other: $type<T>) -> $type<T> {
+                (Vector::from(*self) $op Vector::from(other)).into()
+            }
+        }
+
+        impl<'a, 'b, T: $trait<Output = T> + Copy> $trait<&'b $type<T>> for &'a $type<T> {
+            type Output = $type<T>;
+            fn $fn(self, other: &'b $type<T>) -> $type<T> {
+                (Vector::from(*self) $op Vector::from(*other)).into()
             }
         }
 
         // Position <op> scalar
-        impl<T, U> $trait<U> for Position<T>
+        impl<T, U> $trait<U> for $type<T>
         where
             T: $trait<Output = T> + Copy,
             U: Scalar + Copy + Into<T>,
         {
-            type Output = Position<T>;
-            fn $fn(self, scalar: U) -> Position<T> {
-                let scalar = scalar.into();
-                Position {
-                    row: self.row $op scalar,
-                    column: self.column $op scalar,
-                }
+            type Output = $type<T>;
+            fn $fn(self, scalar: U) -> $type<T> {
+                (Vector::from(self) $op scalar).into()
             }
         }
 
-        impl<'a, T, U> $trait<U> for &'a Position<T>
+        impl<'a, T, U> $trait<U> for &'a $type<T>
         where
             T: $trait<Output = T> + Copy,
             U: Scalar + Copy + Into<T>,
         {
-            type Output = Position<T>;
-            fn $fn(self, scalar: U) -> Position<T> {
-                let scalar = scalar.into();
-                Position {
-                    row: self.row $op scalar,
-                    column: self.column $op scalar,
-                }
+            type Output = $type<T>;
+            fn $fn(self, scalar: U) -> $type<T> {
+                (Vector::from(*self) $op scalar).into()
             }
         }
     };
 }
 
-impl_ops!(Add, add, +);
-impl_ops!(Sub, sub, -);
-impl_ops!(Mul, mul, *);
-impl_ops!(Div, div, /);
+impl_ops!(Position, Add, add, +);
+impl_ops!(Position, Sub, sub, -);
+impl_ops!(Position, Mul, mul, *);
+impl_ops!(Position, Div, div, /);
+
+impl_ops!(Position3, Add, add, +);
+impl_ops!(Position3, Sub, sub, -);
+impl_ops!(Position3, Mul, mul, *);
+impl_ops!(Position3, Div, div, /);
 
 #[cfg(test)]
 mod tests {
@@ -134,6 +683,267 @@ mod tests {
         assert_eq!(pos.column, 2 as i16);
     }
 
+    #[test]
+    fn it_converts_between_integer_types() {
+        let signed = Position::<i64> { row: 3, column: 4 };
+        let unsigned = Position::<usize>::try_from(signed).unwrap();
+        assert_eq!(unsigned, Position { row: 3, column: 4 });
+        assert_eq!(Position::<i64>::try_from(unsigned), Ok(signed));
+
+        let negative = Position::<i32> { row: -1, column: 4 };
+        assert_eq!(
+            Position::<usize>::try_from(negative),
+            Err(TryFromPositionError)
+        );
+        let large = Position::<u32> {
+            row: 300,
+            column: 4,
+        };
+        assert!(Position::<u8>::try_from(large).is_err());
+    }
+
+    #[test]
+    fn it_offsets_within_a_boundary() {
+        let boundary = Position { row: 3, column: 4 };
+        let corner = Position { row: 0, column: 3 };
+        let up_right = Position { row: -1, column: 1 };
+        let down_left = Position { row: 2, column: -3 };
+
+        assert_eq!(corner.checked_offset(&up_right, &boundary), None);
+        assert_eq!(
+            corner.checked_offset(&down_left, &boundary),
+            Some(Position { row: 2, column: 0 })
+        );
+        assert_eq!(
+            corner.wrapping_offset(&up_right, &boundary),
+            Some(Position { row: 2, column: 0 })
+        );
+        assert_eq!(
+            corner.saturating_offset(&up_right, &boundary),
+            Some(Position { row: 0, column: 3 })
+        );
+        assert_eq!(
+            corner.saturating_offset(&Position { row: 9, column: -9 }, &boundary),
+            Some(Position { row: 2, column: 0 })
+        );
+        assert_eq!(
+            corner.wrapping_offset(&up_right, &Position { row: 0, column: 4 }),
+            None
+        );
+
+        let room = Rect::new(
+            Position { row: 1, column: 1 },
+            Position { row: 3, column: 4 },
+        );
+        let inside = Position { row: 1, column: 3 };
+        assert_eq!(inside.checked_offset(&up_right, &room), None);
+        assert_eq!(
+            inside.wrapping_offset(&up_right, &room),
+            Some(Position { row: 2, column: 1 })
+        );
+        assert_eq!(
+            inside.saturating_offset(&down_left, &room),
+            Some(Position { row: 2, column: 1 })
+        );
+    }
+
+    #[test]
+    fn it_measures_distances() {
+        let a = Position { row: 1, column: -2 };
+        let b = Position { row: 4, column: 2 };
+
+        assert_eq!(Position::manhattan_distance(&a, &b), 7);
+        assert_eq!(Position::chebyshev_distance(&a, &b), 4);
+        assert_eq!(Position::squared_euclidean_distance(&a, &b), 25);
+
+        let a = Position {
+            row: 0.5,
+            column: 1.0,
+        };
+        let b = Position {
+            row: 2.0,
+            column: 0.0,
+        };
+        assert_eq!(Position::manhattan_distance(&a, &b), 2.5);
+
+        let a: Position<usize> = Position { row: 0, column: 0 };
+        let b = Position { row: 1, column: 2 };
+        assert_eq!(Position::squared_euclidean_distance(&a, &b), 5);
+        assert_eq!(Position::squared_euclidean_distance(&b, &a), 5);
+        assert_eq!(Position::chebyshev_distance(&a, &b), 2);
+    }
+
+    #[test]
+    fn it_finds_products() {
+        let right = Position { row: 0, column: 1 };
+        let down = Position { row: 1, column: 0 };
+
+        assert_eq!(right.dot(&down), 0);
+        assert_eq!(right.dot(&Position { row: 3, column: 2 }), 2);
+        assert_eq!(right.cross(&down), 1);
+        assert_eq!(down.cross(&right), -1);
+
+        // A clockwise square with sides of length 2
+        let corners = [
+            Position { row: 0, column: 0 },
+            Position { row: 0, column: 2 },
+            Position { row: 2, column: 2 },
+            Position { row: 2, column: 0 },
+        ];
+        let twice_area: i32 = (0..4)
+            .map(|i| corners[i].cross(&corners[(i + 1) % 4]))
+            .sum();
+        assert_eq!(twice_area, 8);
+    }
+
+    #[test]
+    fn it_rotates_and_reflects() {
+        let right = Position { row: 0, column: 1 };
+        let down = Position { row: 1, column: 0 };
+
+        assert_eq!(right.rotate_clockwise(), down);
+        assert_eq!(down.rotate_anticlockwise(), right);
+        assert_eq!(
+            right.rotate_clockwise().rotate_clockwise(),
+            Position { row: 0, column: -1 }
+        );
+
+        let pivot = Position { row: 5, column: 5 };
+        let point = Position { row: 5, column: 7 };
+        assert_eq!(
+            point.rotate_clockwise_about(&pivot),
+            Position { row: 7, column: 5 }
+        );
+        assert_eq!(
+            point.rotate_anticlockwise_about(&pivot),
+            Position { row: 3, column: 5 }
+        );
+
+        let antenna = Position { row: 3, column: 4 };
+        let other = Position { row: 5, column: 5 };
+        assert_eq!(
+            other.reflect_about(&antenna),
+            Position { row: 1, column: 3 }
+        );
+        assert_eq!(point.flip_vertical(), Position { row: -5, column: 7 });
+        assert_eq!(point.flip_horizontal(), Position { row: 5, column: -7 });
+    }
+
+    #[test]
+    fn it_finds_signs_and_extremes() {
+        let a = Position { row: -3, column: 2 };
+        let b = Position { row: 1, column: 0 };
+
+        assert_eq!(a.abs(), Position { row: 3, column: 2 });
+        assert_eq!(a.signum(), Position { row: -1, column: 1 });
+        assert_eq!(a.component_min(&b), Position { row: -3, column: 0 });
+        assert_eq!(a.component_max(&b), Position { row: 1, column: 2 });
+    }
+
+    #[test]
+    fn it_parses_and_displays() {
+        let position: Position<i32> = "3,-4".parse().unwrap();
+        assert_eq!(position, Position { row: 3, column: -4 });
+        assert_eq!(position.to_string(), "(3, -4)");
+        assert_eq!(position.to_string().parse(), Ok(position));
+
+        assert_eq!(
+            Position::<usize>::parse("p=0,4".trim_start_matches("p="), Axes::XY),
+            Ok(Position { row: 4, column: 0 })
+        );
+        assert_eq!(
+            "34".parse::<Position<usize>>(),
+            Err(ParsePositionError::MissingComma)
+        );
+        assert!(matches!(
+            "3,x".parse::<Position<usize>>(),
+            Err(ParsePositionError::InvalidCoordinate(_))
+        ));
+    }
+
+    #[test]
+    fn it_orders_positions_in_reading_order() {
+        let mut positions = vec![
+            Position { row: 1, column: 0 },
+            Position { row: 0, column: 5 },
+            Position { row: 0, column: 2 },
+        ];
+        positions.sort();
+        assert_eq!(
+            positions,
+            vec![
+                Position { row: 0, column: 2 },
+                Position { row: 0, column: 5 },
+                Position { row: 1, column: 0 },
+            ]
+        );
+        assert_eq!(Position::<u8>::default(), Position { row: 0, column: 0 });
+    }
+
+    #[test]
+    fn it_converts_tuples_and_arrays() {
+        let position = Position::from((1, 2));
+        assert_eq!(position, Position { row: 1, column: 2 });
+        assert_eq!(Position::from([1, 2]), position);
+        assert_eq!(<(i32, i32)>::from(position), (1, 2));
+        assert_eq!(<[i32; 2]>::from(position), [1, 2]);
+    }
+
+    #[test]
+    fn it_iterates_over_balls() {
+        let centre = Position { row: 5, column: -3 };
+
+        assert_eq!(centre.ball(2, Metric::Manhattan).count(), 13);
+        assert_eq!(centre.ring(2, Metric::Manhattan).count(), 8);
+        assert_eq!(centre.ring(0, Metric::Manhattan).count(), 1);
+        assert_eq!(centre.ball(2, Metric::Chebyshev).count(), 25);
+        assert_eq!(centre.ring(2, Metric::Chebyshev).count(), 16);
+        assert_eq!(centre.ball(-1, Metric::Chebyshev).count(), 0);
+
+        for metric in [Metric::Manhattan, Metric::Chebyshev] {
+            assert!(
+                centre
+                    .ball(3, metric)
+                    .all(|(position, distance)| distance <= 3
+                        && distance == metric.distance(&centre, &position))
+            );
+            assert!(centre.ring(3, metric).all(|(_, distance)| distance == 3));
+        }
+    }
+
+    #[test]
+    fn it_clips_balls_to_a_grid() {
+        let boundary = Position {
+            row: 10,
+            column: 10,
+        };
+        let corner = Position { row: 0, column: 0 };
+
+        assert_eq!(
+            corner
+                .ball_within(2, Metric::Manhattan, &boundary)
+                .collect::<Vec<_>>(),
+            vec![
+                (Position { row: 0, column: 0 }, 0),
+                (Position { row: 0, column: 1 }, 1),
+                (Position { row: 0, column: 2 }, 2),
+                (Position { row: 1, column: 0 }, 1),
+                (Position { row: 1, column: 1 }, 2),
+                (Position { row: 2, column: 0 }, 2),
+            ]
+        );
+        assert_eq!(
+            corner.ring_within(2, Metric::Chebyshev, &boundary).count(),
+            5
+        );
+        assert_eq!(
+            Position { row: 9, column: 5 }
+                .ring_within(1, Metric::Manhattan, &boundary)
+                .count(),
+            3
+        );
+    }
+
     #[test]
     fn it_adds_positions() {
         let pos_1 = Position { row: 1, column: 2 };
@@ -210,4 +1020,15 @@ mod tests {
         assert_eq!(result.row, 0.5);
         assert_eq!(result.column, 1.0);
     }
+
+    #[test]
+    fn it_does_arithmetic_in_three_dimensions() {
+        let a = Position3 { x: 1, y: 2, z: 3 };
+        let b = Position3 { x: 4, y: -6, z: 8 };
+
+        assert_eq!(a + b, Position3 { x: 5, y: -4, z: 11 });
+        assert_eq!(b - a, Position3 { x: 3, y: -8, z: 5 });
+        assert_eq!(a * 2, Position3 { x: 2, y: 4, z: 6 });
+        assert_eq!(Position3::manhattan_distance(&a, &b), 16);
+    }
 }
@@ -0,0 +1,336 @@
+use crate::position::Position;
+use crate::traits::{One, Zero};
+use std::ops::{Add, Div, Mul, Sub};
+
+/// An axis-aligned rectangle of positions, from `min` up to but not including
+/// `max`. The boundary taken by `Direction::travel_with_bounds` is the same
+/// as a rectangle starting at zero. `Cuboid` instead includes its `max`
+/// corner, as puzzle inputs give boxes by their two corner cubes, so build
+/// either from corners with `from_corners` rather than setting `max` by hand.
+#[derive(Eq, PartialEq, Clone, Copy, Hash, Debug, Default)]
+pub struct Rect<T = usize> {
+    pub min: Position<T>,
+    pub max: Position<T>,
+}
+
+impl<T: Copy + PartialOrd> Rect<T> {
+    /// The rectangle from `min` up to but not including `max`.
+    pub fn new(min: Position<T>, max: Position<T>) -> Self {
+        Rect { min, max }
+    }
+
+    /// The rectangle between two opposite corners, in any order, including
+    /// both corners. This matches `Cuboid::from_corners`.
+    pub fn from_corners(a: &Position<T>, b: &Position<T>) -> Self
+    where
+        T: Add<Output = T> + One,
+    {
+        let min = a.component_min(b);
+        let max = a.component_max(b);
+        Rect {
+            min,
+            max: Position {
+                row: max.row + T::one(),
+                column: max.column + T::one(),
+            },
+        }
+    }
+
+    /// The smallest rectangle which contains every point, or `None` if there
+    /// are no points.
+    pub fn bounding<'a, I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a Position<T>>,
+        T: Add<Output = T> + One + 'a,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min, max) = points.fold((*first, *first), |(min, max), point| {
+            (min.component_min(point), max.component_max(point))
+        });
+        Some(Rect::from_corners(&min, &max))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.row >= self.max.row || self.min.column >= self.max.column
+    }
+
+    pub fn contains(&self, position: &Position<T>) -> bool {
+        (self.min.row..self.max.row).contains(&position.row)
+            && (self.min.column..self.max.column).contains(&position.column)
+    }
+
+    /// Whether every position of the other rectangle is also in this one.
+    pub fn contains_rect(&self, other: &Rect<T>) -> bool {
+        other.is_empty()
+            || (self.min.row <= other.min.row
+                && self.min.column <= other.min.column
+                && other.max.row <= self.max.row
+                && other.max.column <= self.max.column)
+    }
+
+    /// The positions which are in both rectangles, or `None` if there are
+    /// none.
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>> {
+        let intersection = Rect {
+            min: self.min.component_max(&other.min),
+            max: self.max.component_min(&other.max),
+        };
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    pub fn intersects(&self, other: &Rect<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The smallest rectangle which contains both rectangles.
+    pub fn union(&self, other: &Rect<T>) -> Rect<T> {
+        if other.is_empty() {
+            return *self;
+        }
+        if self.is_empty() {
+            return *other;
+        }
+        Rect {
+            min: self.min.component_min(&other.min),
+            max: self.max.component_max(&other.max),
+        }
+    }
+
+    /// The number of rows and columns in the rectangle.
+    pub fn size(&self) -> Position<T>
+    where
+        T: Sub<Output = T> + Zero,
+    {
+        if self.is_empty() {
+            Position {
+                row: T::zero(),
+                column: T::zero(),
+            }
+        } else {
+            self.max - self.min
+        }
+    }
+
+    /// The number of positions in the rectangle.
+    pub fn area(&self) -> T
+    where
+        T: Sub<Output = T> + Mul<Output = T> + Zero,
+    {
+        let size = self.size();
+        size.row * size.column
+    }
+
+    /// Splits the rectangle into top left, top right, bottom left and bottom
+    /// right quarters. When there are an odd number of rows or columns, the
+    /// middle one is left out of every quarter.
+    pub fn quadrants(&self) -> [Rect<T>; 4]
+    where
+        T: Add<Output = T> + Sub<Output = T> + Div<Output = T> + One + Zero,
+    {
+        let two = T::one() + T::one();
+        let size = self.size();
+        let half = Position {
+            row: size.row / two,
+            column: size.column / two,
+        };
+        let low_end = self.min + half;
+        let high_start = self.max - half;
+
+        let rows = [(self.min.row, low_end.row), (high_start.row, self.max.row)];
+        let columns = [
+            (self.min.column, low_end.column),
+            (high_start.column, self.max.column),
+        ];
+        [(0, 0), (0, 1), (1, 0), (1, 1)].map(|(row, column)| Rect {
+            min: Position {
+                row: rows[row].0,
+                column: columns[column].0,
+            },
+            max: Position {
+                row: rows[row].1,
+                column: columns[column].1,
+            },
+        })
+    }
+
+    /// Every position in the rectangle, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position<T>> + use<T>
+    where
+        T: Add<Output = T> + One,
+    {
+        let Rect { min, max } = *self;
+        let step = |value: &T| Some(*value + T::one());
+        std::iter::successors(Some(min.row), step)
+            .take_while(move |row| *row < max.row)
+            .flat_map(move |row| {
+                std::iter::successors(Some(min.column), step)
+                    .take_while(move |column| *column < max.column)
+                    .map(move |column| Position { row, column })
+            })
+    }
+}
+
+/// A boundary is the rectangle from zero up to it.
+impl<T: Zero> From<Position<T>> for Rect<T> {
+    fn from(boundary: Position<T>) -> Self {
+        Rect {
+            min: Position {
+                row: T::zero(),
+                column: T::zero(),
+            },
+            max: boundary,
+        }
+    }
+}
+
+impl<T: Copy + Zero> From<&Position<T>> for Rect<T> {
+    fn from(boundary: &Position<T>) -> Self {
+        Rect::from(*boundary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_builds_a_bounding_box() {
+        let points = [
+            Position { row: 3, column: -1 },
+            Position { row: -2, column: 4 },
+            Position { row: 0, column: 0 },
+        ];
+        let rect = Rect::bounding(&points).unwrap();
+
+        assert_eq!(
+            rect,
+            Rect::new(
+                Position {
+                    row: -2,
+                    column: -1
+                },
+                Position { row: 4, column: 5 }
+            )
+        );
+        assert!(points.iter().all(|point| rect.contains(point)));
+        assert!(!rect.contains(&Position { row: 4, column: 0 }));
+        assert_eq!(rect.area(), 36);
+        assert_eq!(Rect::<i32>::bounding(&[]), None);
+    }
+
+    #[test]
+    fn it_builds_from_inclusive_corners() {
+        let rect = Rect::from_corners(
+            &Position { row: 3, column: -1 },
+            &Position { row: -2, column: 4 },
+        );
+
+        assert_eq!(
+            rect,
+            Rect::new(
+                Position {
+                    row: -2,
+                    column: -1
+                },
+                Position { row: 4, column: 5 }
+            )
+        );
+        assert!(rect.contains(&Position { row: 3, column: 4 }));
+        assert_eq!(
+            Rect::from_corners(
+                &Position { row: 1, column: 1 },
+                &Position { row: 1, column: 1 }
+            )
+            .area(),
+            1
+        );
+    }
+
+    #[test]
+    fn it_combines_rectangles() {
+        let a = Rect::new(
+            Position { row: 0, column: 0 },
+            Position { row: 4, column: 4 },
+        );
+        let b = Rect::new(
+            Position { row: 2, column: 3 },
+            Position { row: 6, column: 5 },
+        );
+        let c = Rect::new(
+            Position { row: 4, column: 0 },
+            Position { row: 5, column: 1 },
+        );
+
+        assert_eq!(
+            a.intersection(&b),
+            Some(Rect::new(
+                Position { row: 2, column: 3 },
+                Position { row: 4, column: 4 }
+            ))
+        );
+        assert!(!a.intersects(&c));
+        assert_eq!(
+            a.union(&b),
+            Rect::new(
+                Position { row: 0, column: 0 },
+                Position { row: 6, column: 5 }
+            )
+        );
+        assert!(a.union(&c).contains_rect(&c));
+    }
+
+    #[test]
+    fn it_splits_into_quadrants() {
+        let room = Rect::from(Position { row: 7, column: 11 });
+        let quadrants = room.quadrants();
+
+        assert_eq!(
+            quadrants[0],
+            Rect::new(
+                Position { row: 0, column: 0 },
+                Position { row: 3, column: 5 }
+            )
+        );
+        assert_eq!(
+            quadrants[3],
+            Rect::new(
+                Position { row: 4, column: 6 },
+                Position { row: 7, column: 11 }
+            )
+        );
+        assert!(quadrants.iter().all(|quadrant| quadrant.area() == 15));
+        assert!(
+            quadrants
+                .iter()
+                .all(|quadrant| !quadrant.contains(&Position { row: 3, column: 0 }))
+        );
+
+        let even = Rect::from(Position { row: 4, column: 4 });
+        let total: usize = even
+            .quadrants()
+            .iter()
+            .map(|quadrant| quadrant.area())
+            .sum();
+        assert_eq!(total, 16);
+    }
+
+    #[test]
+    fn it_iterates_over_cells() {
+        let rect = Rect::new(
+            Position { row: -1, column: 2 },
+            Position { row: 1, column: 4 },
+        );
+
+        assert_eq!(
+            rect.positions().collect::<Vec<_>>(),
+            vec![
+                Position { row: -1, column: 2 },
+                Position { row: -1, column: 3 },
+                Position { row: 0, column: 2 },
+                Position { row: 0, column: 3 },
+            ]
+        );
+        assert_eq!(Rect::<u8>::default().positions().count(), 0);
+    }
+}
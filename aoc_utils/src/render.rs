@@ -0,0 +1,333 @@
+use crate::grid::Grid;
+use crate::position::Position;
+use crate::rect::Rect;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub struct Colour {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Colour {
+    pub const BLACK: Colour = Colour::new(0, 0, 0);
+    pub const WHITE: Colour = Colour::new(255, 255, 255);
+    pub const RED: Colour = Colour::new(255, 0, 0);
+    pub const GREEN: Colour = Colour::new(0, 255, 0);
+    pub const BLUE: Colour = Colour::new(0, 0, 255);
+
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Colour { red, green, blue }
+    }
+}
+
+/// An RGB image, used to dump grids for visual debugging.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Colour>,
+}
+
+impl Image {
+    /// Draws one block of `scale` by `scale` pixels per cell of the grid.
+    pub fn from_grid<T, F>(grid: &Grid<T>, mut mapper: F, scale: usize) -> Self
+    where
+        F: FnMut(&T) -> Colour,
+    {
+        let dimensions = grid.dimensions();
+        let colours: Vec<Colour> = grid.as_slice().iter().map(&mut mapper).collect();
+        Image::from_colours(&colours, dimensions.column, dimensions.row, scale)
+    }
+
+    /// Draws a set of points in the foreground colour on a background which
+    /// just covers them, with one block of `scale` by `scale` pixels each.
+    pub fn from_points<I>(points: I, foreground: Colour, background: Colour, scale: usize) -> Self
+    where
+        I: IntoIterator<Item = Position<i64>>,
+    {
+        let points: Vec<Position<i64>> = points.into_iter().collect();
+        let Some(Rect { min, max }) = Rect::bounding(&points) else {
+            return Image::from_colours(&[], 0, 0, scale);
+        };
+
+        let width = (max.column - min.column) as usize;
+        let height = (max.row - min.row) as usize;
+        let mut colours = vec![background; width * height];
+        for point in points {
+            let row = (point.row - min.row) as usize;
+            let column = (point.column - min.column) as usize;
+            colours[row * width + column] = foreground;
+        }
+
+        Image::from_colours(&colours, width, height, scale)
+    }
+
+    fn from_colours(colours: &[Colour], width: usize, height: usize, scale: usize) -> Self {
+        let scale = scale.max(1);
+        let mut pixels = Vec::with_capacity(colours.len() * scale * scale);
+        for row in 0..height {
+            let cells = &colours[row * width..(row + 1) * width];
+            for _ in 0..scale {
+                for colour in cells {
+                    pixels.extend(std::iter::repeat_n(*colour, scale));
+                }
+            }
+        }
+
+        Image {
+            width: width * scale,
+            height: height * scale,
+            pixels,
+        }
+    }
+
+    pub fn dimensions(&self) -> Position {
+        Position {
+            row: self.height,
+            column: self.width,
+        }
+    }
+
+    pub fn get(&self, position: &Position) -> Option<Colour> {
+        if position.row < self.height && position.column < self.width {
+            Some(self.pixels[position.row * self.width + position.column])
+        } else {
+            None
+        }
+    }
+
+    /// Encodes the image as a binary PPM, which almost any image viewer can
+    /// open.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for pixel in &self.pixels {
+            bytes.extend([pixel.red, pixel.green, pixel.blue]);
+        }
+        bytes
+    }
+
+    /// Encodes the image as a PNG. The pixel data is stored uncompressed so
+    /// that no compression library is needed, which makes the files larger
+    /// than they would otherwise be.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut header = vec![];
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, deflate, adaptive filtering, no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        // Every scanline starts with a filter type, which is always "none"
+        let mut scanlines = Vec::with_capacity(self.height * (self.width * 3 + 1));
+        for row in self.pixels.chunks(self.width.max(1)) {
+            scanlines.push(0);
+            for pixel in row {
+                scanlines.extend([pixel.red, pixel.green, pixel.blue]);
+            }
+        }
+
+        let mut bytes = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        write_png_chunk(&mut bytes, b"IHDR", &header);
+        write_png_chunk(&mut bytes, b"IDAT", &zlib_store(&scanlines));
+        write_png_chunk(&mut bytes, b"IEND", &[]);
+        bytes
+    }
+
+    /// Renders the image for a terminal which supports 24-bit colour, using
+    /// two character cells per pixel so that pixels come out roughly square.
+    pub fn to_ansi(&self) -> String {
+        let mut output = String::new();
+        for row in self.pixels.chunks(self.width.max(1)) {
+            for pixel in row {
+                output.push_str(&format!(
+                    "\x1b[48;2;{};{};{}m  ",
+                    pixel.red, pixel.green, pixel.blue
+                ));
+            }
+            output.push_str("\x1b[0m\n");
+        }
+        output
+    }
+
+    pub fn write_ppm<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_ppm())
+    }
+
+    pub fn write_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_png())
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn to_image<F>(&self, mapper: F, scale: usize) -> Image
+    where
+        F: FnMut(&T) -> Colour,
+    {
+        Image::from_grid(self, mapper, scale)
+    }
+
+    /// Like `print`, but with a 24-bit terminal colour behind each character.
+    pub fn print_ansi<F>(&self, mut mapper: F)
+    where
+        F: FnMut(&T) -> (char, Colour),
+    {
+        self.rows().for_each(|row| {
+            let line: String = row
+                .iter()
+                .map(|cell| {
+                    let (character, colour) = mapper(cell);
+                    format!(
+                        "\x1b[48;2;{};{};{}m{}",
+                        colour.red, colour.green, colour.blue, character
+                    )
+                })
+                .collect();
+            println!("{}\x1b[0m", line);
+        });
+    }
+}
+
+fn write_png_chunk(bytes: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    bytes.extend((data.len() as u32).to_be_bytes());
+    let start = bytes.len();
+    bytes.extend(kind);
+    bytes.extend(data);
+    let crc = crc32(&bytes[start..]);
+    bytes.extend(crc.to_be_bytes());
+}
+
+/// Wraps data in a zlib stream made of uncompressed deflate blocks.
+fn zlib_store(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        let length = block.len() as u16;
+        stream.push(is_final as u8);
+        stream.extend(length.to_le_bytes());
+        stream.extend((!length).to_le_bytes());
+        stream.extend(block);
+    }
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Image {
+        let grid = Grid::new(vec![vec![true, false], vec![false, true]]);
+        grid.to_image(|&on| if on { Colour::WHITE } else { Colour::BLACK }, 2)
+    }
+
+    #[test]
+    fn it_upscales() {
+        let image = checkerboard();
+
+        assert_eq!(image.dimensions(), Position { row: 4, column: 4 });
+        assert_eq!(
+            image.get(&Position { row: 1, column: 1 }),
+            Some(Colour::WHITE)
+        );
+        assert_eq!(
+            image.get(&Position { row: 1, column: 2 }),
+            Some(Colour::BLACK)
+        );
+        assert_eq!(
+            image.get(&Position { row: 3, column: 3 }),
+            Some(Colour::WHITE)
+        );
+        assert_eq!(image.get(&Position { row: 4, column: 0 }), None);
+    }
+
+    #[test]
+    fn it_draws_points() {
+        let points = [
+            Position { row: -1, column: 5 },
+            Position { row: 1, column: 6 },
+        ];
+        let image = Image::from_points(points, Colour::GREEN, Colour::BLACK, 1);
+
+        assert_eq!(image.dimensions(), Position { row: 3, column: 2 });
+        assert_eq!(
+            image.get(&Position { row: 0, column: 0 }),
+            Some(Colour::GREEN)
+        );
+        assert_eq!(
+            image.get(&Position { row: 2, column: 1 }),
+            Some(Colour::GREEN)
+        );
+        assert_eq!(
+            image.get(&Position { row: 1, column: 0 }),
+            Some(Colour::BLACK)
+        );
+    }
+
+    #[test]
+    fn it_encodes_ppm() {
+        let ppm = checkerboard().to_ppm();
+
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+        assert_eq!(&ppm[11..14], &[255, 255, 255]);
+    }
+
+    #[test]
+    fn it_encodes_png() {
+        let png = checkerboard().to_png();
+
+        assert_eq!(
+            &png[..8],
+            &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']
+        );
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 4, 0, 0, 0, 4]);
+        // The end chunk has no data, so always has the same checksum
+        assert_eq!(
+            &png[png.len() - 12..],
+            &[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]
+        );
+    }
+
+    #[test]
+    fn it_computes_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn it_renders_ansi() {
+        let grid = Grid::new(vec![vec![Colour::RED]]);
+        let ansi = grid.to_image(|&colour| colour, 1).to_ansi();
+
+        assert_eq!(ansi, "\x1b[48;2;255;0;0m  \x1b[0m\n");
+    }
+}
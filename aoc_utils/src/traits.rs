@@ -43,3 +43,40 @@ macro_rules! impl_constants {
     };
 }
 impl_constants!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+macro_rules! impl_float_constants {
+    ($($t:ty),*) => {
+        $(
+            impl Zero for $t {
+                fn zero() -> Self {
+                    0.0
+                }
+            }
+            impl One for $t {
+                fn one() -> Self {
+                    1.0
+                }
+            }
+        )*
+    };
+}
+impl_float_constants!(f32, f64);
+
+pub trait Signed {
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+}
+macro_rules! impl_signed {
+    ($($t:ty),*) => {
+        $(
+            impl Signed for $t {
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+                fn signum(self) -> Self {
+                    <$t>::signum(self)
+                }
+            }
+        )*
+    };
+}
+impl_signed!(i8, i16, i32, i64, i128, isize, f32, f64);
@@ -0,0 +1,300 @@
+use crate::position::{Position, Position3, Scalar};
+use crate::traits::{Signed, Zero};
+use std::ops::{Add, Div, Index, IndexMut, Mul, Neg, Sub};
+
+/// A point or offset with any number of components. `Position` and
+/// `Position3` convert to and from the two and three component versions, and
+/// do their arithmetic through them.
+#[derive(Eq, PartialEq, Clone, Copy, Hash, Debug)]
+pub struct Vector<T, const N: usize>(pub [T; N]);
+
+impl<T, const N: usize> Vector<T, N> {
+    pub fn new(components: [T; N]) -> Self {
+        Vector(components)
+    }
+
+    /// A vector with every component set to the same value.
+    pub fn splat(value: T) -> Self
+    where
+        T: Copy,
+    {
+        Vector([value; N])
+    }
+
+    pub fn components(&self) -> &[T; N] {
+        &self.0
+    }
+
+    /// Applies a function to every component.
+    pub fn map<U, F>(&self, f: F) -> Vector<U, N>
+    where
+        T: Copy,
+        F: FnMut(T) -> U,
+    {
+        Vector(self.0.map(f))
+    }
+
+    fn zip_with<F>(&self, other: &Vector<T, N>, mut f: F) -> Vector<T, N>
+    where
+        T: Copy,
+        F: FnMut(T, T) -> T,
+    {
+        Vector(std::array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+
+    pub fn dot(&self, other: &Vector<T, N>) -> T
+    where
+        T: Add<Output = T> + Mul<Output = T> + Copy + Zero,
+    {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .fold(T::zero(), |sum, (&a, &b)| sum + a * b)
+    }
+
+    pub fn abs(&self) -> Self
+    where
+        T: Signed + Copy,
+    {
+        self.map(T::abs)
+    }
+
+    pub fn signum(&self) -> Self
+    where
+        T: Signed + Copy,
+    {
+        self.map(T::signum)
+    }
+
+    /// The smallest of each component of the two vectors.
+    pub fn component_min(&self, other: &Vector<T, N>) -> Self
+    where
+        T: PartialOrd + Copy,
+    {
+        self.zip_with(other, |a, b| if b < a { b } else { a })
+    }
+
+    /// The largest of each component of the two vectors.
+    pub fn component_max(&self, other: &Vector<T, N>) -> Self
+    where
+        T: PartialOrd + Copy,
+    {
+        self.zip_with(other, |a, b| if b > a { b } else { a })
+    }
+
+    /// The sum of the absolute values of the components.
+    pub fn manhattan_length(&self) -> T
+    where
+        T: Signed + Add<Output = T> + Copy + Zero,
+    {
+        self.0.iter().fold(T::zero(), |sum, &a| sum + a.abs())
+    }
+}
+
+impl<T: Default, const N: usize> Default for Vector<T, N> {
+    fn default() -> Self {
+        Vector(std::array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Vector<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Vector<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.0[index]
+    }
+}
+
+impl<T: Neg<Output = T> + Copy, const N: usize> Neg for Vector<T, N> {
+    type Output = Vector<T, N>;
+
+    fn neg(self) -> Vector<T, N> {
+        self.map(|a| -a)
+    }
+}
+
+macro_rules! impl_vector_ops {
+    ($trait:ident, $fn:ident, $op:tt) => {
+        // Vector <op> Vector
+        impl<T: $trait<Output = T> + Copy, const N: usize> $trait for Vector<T, N> {
+            type Output = Vector<T, N>;
+            fn $fn(self, other: Vector<T, N>) -> Vector<T, N> {
+                self.zip_with(&other, |a, b| a $op b)
+            }
+        }
+
+        impl<'a, T: $trait<Output = T> + Copy, const N: usize> $trait<&'a Vector<T, N>>
+            for Vector<T, N>
+        {
+            type Output = Vector<T, N>;
+            fn $fn(self, other: &'a Vector<T, N>) -> Vector<T, N> {
+                self.zip_with(other, |a, b| a $op b)
+            }
+        }
+
+        impl<'b, T: $trait<Output = T> + Copy, const N: usize> $trait<Vector<T, N>>
+            for &'b Vector<T, N>
+        {
+            type Output = Vector<T, N>;
+            fn $fn(self, other: Vector<T, N>) -> Vector<T, N> {
+                self.zip_with(&other, |a, b| a $op b)
+            }
+        }
+
+        impl<'a, 'b, T: $trait<Output = T> + Copy, const N: usize> $trait<&'b Vector<T, N>>
+            for &'a Vector<T, N>
+        {
+            type Output = Vector<T, N>;
+            fn $fn(self, other: &'b Vector<T, N>) -> Vector<T, N> {
+                self.zip_with(other, |a, b| a $op b)
+            }
+        }
+
+        // Vector <op> scalar
+        impl<T, U, const N: usize> $trait<U> for Vector<T, N>
+        where
+            T: $trait<Output = T> + Copy,
+            U: Scalar + Copy + Into<T>,
+        {
+            type Output = Vector<T, N>;
+            fn $fn(self, scalar: U) -> Vector<T, N> {
+                let scalar = scalar.into();
+                self.map(|a| a $op scalar)
+            }
+        }
+
+        impl<'a, T, U, const N: usize> $trait<U> for &'a Vector<T, N>
+        where
+            T: $trait<Output = T> + Copy,
+            U: Scalar + Copy + Into<T>,
+        {
+            type Output = Vector<T, N>;
+            fn $fn(self, scalar: U) -> Vector<T, N> {
+                let scalar = scalar.into();
+                self.map(|a| a $op scalar)
+            }
+        }
+    };
+}
+
+impl_vector_ops!(Add, add, +);
+impl_vector_ops!(Sub, sub, -);
+impl_vector_ops!(Mul, mul, *);
+impl_vector_ops!(Div, div, /);
+
+impl<T, const N: usize> From<[T; N]> for Vector<T, N> {
+    fn from(components: [T; N]) -> Self {
+        Vector(components)
+    }
+}
+
+impl<T, const N: usize> From<Vector<T, N>> for [T; N] {
+    fn from(vector: Vector<T, N>) -> Self {
+        vector.0
+    }
+}
+
+impl<T> From<(T, T)> for Vector<T, 2> {
+    fn from((a, b): (T, T)) -> Self {
+        Vector([a, b])
+    }
+}
+
+impl<T> From<Vector<T, 2>> for (T, T) {
+    fn from(Vector([a, b]): Vector<T, 2>) -> Self {
+        (a, b)
+    }
+}
+
+impl<T> From<(T, T, T)> for Vector<T, 3> {
+    fn from((a, b, c): (T, T, T)) -> Self {
+        Vector([a, b, c])
+    }
+}
+
+impl<T> From<Vector<T, 3>> for (T, T, T) {
+    fn from(Vector([a, b, c]): Vector<T, 3>) -> Self {
+        (a, b, c)
+    }
+}
+
+/// The row comes first and the column second.
+impl<T> From<Position<T>> for Vector<T, 2> {
+    fn from(position: Position<T>) -> Self {
+        Vector([position.row, position.column])
+    }
+}
+
+impl<T> From<Vector<T, 2>> for Position<T> {
+    fn from(Vector([row, column]): Vector<T, 2>) -> Self {
+        Position { row, column }
+    }
+}
+
+impl<T> From<Position3<T>> for Vector<T, 3> {
+    fn from(position: Position3<T>) -> Self {
+        Vector([position.x, position.y, position.z])
+    }
+}
+
+impl<T> From<Vector<T, 3>> for Position3<T> {
+    fn from(Vector([x, y, z]): Vector<T, 3>) -> Self {
+        Position3 { x, y, z }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_does_arithmetic() {
+        let a = Vector([1, -2, 3, 4]);
+        let b = Vector([5, 6, -7, 8]);
+
+        assert_eq!(a + b, Vector([6, 4, -4, 12]));
+        assert_eq!(b - a, Vector([4, 8, -10, 4]));
+        assert_eq!(a * b, Vector([5, -12, -21, 32]));
+        assert_eq!(a * 2, Vector([2, -4, 6, 8]));
+        assert_eq!(-a, Vector([-1, 2, -3, -4]));
+        assert_eq!(a.dot(&b), 5 - 12 - 21 + 32);
+    }
+
+    #[test]
+    fn it_finds_signs_and_extremes() {
+        let a = Vector([-3, 0, 5]);
+        let b = Vector([1, -1, 9]);
+
+        assert_eq!(a.abs(), Vector([3, 0, 5]));
+        assert_eq!(a.signum(), Vector([-1, 0, 1]));
+        assert_eq!(a.component_min(&b), Vector([-3, -1, 5]));
+        assert_eq!(a.component_max(&b), Vector([1, 0, 9]));
+        assert_eq!(a.manhattan_length(), 8);
+
+        let f = Vector([1.5, -2.0]);
+        assert_eq!(f.abs(), Vector([1.5, 2.0]));
+        assert_eq!(f.dot(&f), 6.25);
+    }
+
+    #[test]
+    fn it_converts() {
+        let vector: Vector<i32, 2> = (3, 4).into();
+        assert_eq!(<[i32; 2]>::from(vector), [3, 4]);
+        assert_eq!(<(i32, i32)>::from(vector), (3, 4));
+
+        let position: Position<i32> = vector.into();
+        assert_eq!(position, Position { row: 3, column: 4 });
+        assert_eq!(Vector::from(position), vector);
+
+        let position = Position3 { x: 1, y: 2, z: 3 };
+        assert_eq!(Vector::from(position), Vector([1, 2, 3]));
+        assert_eq!(Position3::from(Vector([1, 2, 3])), position);
+        assert_eq!(<(i32, i32, i32)>::from(Vector([1, 2, 3])), (1, 2, 3));
+    }
+}
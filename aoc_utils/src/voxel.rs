@@ -0,0 +1,318 @@
+use crate::position::Position3;
+use crate::traits::One;
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+
+/// A dense three dimensional grid, stored one `z` layer after another, with
+/// each layer stored row by row along `x`.
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub struct VoxelGrid<T> {
+    data: Vec<T>,
+    dimensions: Position3,
+}
+
+impl<T> VoxelGrid<T> {
+    pub fn new(dimensions: Position3, default: T) -> Self
+    where
+        T: Clone,
+    {
+        VoxelGrid {
+            data: vec![default; dimensions.x * dimensions.y * dimensions.z],
+            dimensions,
+        }
+    }
+
+    pub fn index_of(&self, position: &Position3) -> Option<usize> {
+        let Position3 { x, y, z } = self.dimensions;
+        if position.x < x && position.y < y && position.z < z {
+            Some((position.z * y + position.y) * x + position.x)
+        } else {
+            None
+        }
+    }
+
+    pub fn position_of(&self, index: usize) -> Option<Position3> {
+        if index >= self.data.len() {
+            return None;
+        }
+        let Position3 { x, y, .. } = self.dimensions;
+        Some(Position3 {
+            x: index % x,
+            y: index / x % y,
+            z: index / (x * y),
+        })
+    }
+
+    pub fn get(&self, position: &Position3) -> Option<&T> {
+        let index = self.index_of(position)?;
+        self.data.get(index)
+    }
+
+    pub fn get_mut(&mut self, position: &Position3) -> Option<&mut T> {
+        let index = self.index_of(position)?;
+        self.data.get_mut(index)
+    }
+
+    pub fn set(&mut self, position: &Position3, value: T) {
+        if let Some(cell) = self.get_mut(position) {
+            *cell = value;
+        }
+    }
+
+    pub fn dimensions(&self) -> Position3 {
+        self.dimensions
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position3, &T)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (self.position_of(index).unwrap(), value))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position3> + '_ {
+        (0..self.data.len()).map(|index| self.position_of(index).unwrap())
+    }
+
+    /// The cells which share a face with a position.
+    pub fn neighbours(&self, position: &Position3) -> impl Iterator<Item = (Position3, &T)> {
+        self.offset_neighbours(position, FACES)
+    }
+
+    /// The cells which share a face, edge or corner with a position.
+    pub fn all_neighbours(&self, position: &Position3) -> impl Iterator<Item = (Position3, &T)> {
+        self.offset_neighbours(position, all_offsets())
+    }
+
+    fn offset_neighbours<'a, I>(
+        &'a self,
+        position: &Position3,
+        offsets: I,
+    ) -> impl Iterator<Item = (Position3, &'a T)> + use<'a, T, I>
+    where
+        I: IntoIterator<Item = Position3<isize>>,
+    {
+        let position = *position;
+        offsets.into_iter().filter_map(move |offset| {
+            let neighbour = Position3 {
+                x: position.x.checked_add_signed(offset.x)?,
+                y: position.y.checked_add_signed(offset.y)?,
+                z: position.z.checked_add_signed(offset.z)?,
+            };
+            let value = self.get(&neighbour)?;
+            Some((neighbour, value))
+        })
+    }
+}
+
+const FACES: [Position3<isize>; 6] = [
+    Position3 { x: -1, y: 0, z: 0 },
+    Position3 { x: 1, y: 0, z: 0 },
+    Position3 { x: 0, y: -1, z: 0 },
+    Position3 { x: 0, y: 1, z: 0 },
+    Position3 { x: 0, y: 0, z: -1 },
+    Position3 { x: 0, y: 0, z: 1 },
+];
+
+fn all_offsets() -> impl Iterator<Item = Position3<isize>> {
+    (-1..=1)
+        .flat_map(|z| (-1..=1).flat_map(move |y| (-1..=1).map(move |x| Position3 { x, y, z })))
+        .filter(|offset| *offset != Position3 { x: 0, y: 0, z: 0 })
+}
+
+impl<T> Index<&Position3> for VoxelGrid<T> {
+    type Output = T;
+
+    fn index(&self, position: &Position3) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("position {:?} is outside of the grid", position))
+    }
+}
+
+impl<T> IndexMut<&Position3> for VoxelGrid<T> {
+    fn index_mut(&mut self, position: &Position3) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {:?} is outside of the grid", position))
+    }
+}
+
+/// An axis-aligned box, including every point between its two corners. Unlike
+/// `Rect`, whose `max` is one past its last row and column, the `max` corner
+/// is part of the box.
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub struct Cuboid<T = i64> {
+    pub min: Position3<T>,
+    pub max: Position3<T>,
+}
+
+impl<T: Ord + Copy> Cuboid<T> {
+    /// The box between two opposite corners, in any order, including both
+    /// corners. This matches `Rect::from_corners`.
+    pub fn from_corners(a: &Position3<T>, b: &Position3<T>) -> Self {
+        Cuboid {
+            min: Position3 {
+                x: a.x.min(b.x),
+                y: a.y.min(b.y),
+                z: a.z.min(b.z),
+            },
+            max: Position3 {
+                x: a.x.max(b.x),
+                y: a.y.max(b.y),
+                z: a.z.max(b.z),
+            },
+        }
+    }
+
+    /// The box from `min` up to but not including `max`, as taken by
+    /// `Rect::new`. Returns `None` if the box would have no points.
+    pub fn from_exclusive(min: &Position3<T>, max: &Position3<T>) -> Option<Self>
+    where
+        T: Sub<Output = T> + One,
+    {
+        (min.x < max.x && min.y < max.y && min.z < max.z).then(|| Cuboid {
+            min: *min,
+            max: Position3 {
+                x: max.x - T::one(),
+                y: max.y - T::one(),
+                z: max.z - T::one(),
+            },
+        })
+    }
+
+    pub fn contains(&self, position: &Position3<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&position.x)
+            && (self.min.y..=self.max.y).contains(&position.y)
+            && (self.min.z..=self.max.z).contains(&position.z)
+    }
+
+    /// Whether the two boxes share at least one point.
+    pub fn intersects(&self, other: &Cuboid<T>) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+            && self.min.z <= other.max.z
+            && other.min.z <= self.max.z
+    }
+
+    /// The box of points which are in both boxes, or `None` if they do not
+    /// intersect.
+    pub fn intersection(&self, other: &Cuboid<T>) -> Option<Cuboid<T>> {
+        if !self.intersects(other) {
+            return None;
+        }
+        Some(Cuboid {
+            min: Position3 {
+                x: self.min.x.max(other.min.x),
+                y: self.min.y.max(other.min.y),
+                z: self.min.z.max(other.min.z),
+            },
+            max: Position3 {
+                x: self.max.x.min(other.max.x),
+                y: self.max.y.min(other.max.y),
+                z: self.max.z.min(other.max.z),
+            },
+        })
+    }
+
+    /// The number of points in the box.
+    pub fn volume(&self) -> T
+    where
+        T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + One,
+    {
+        let size = self.max - self.min;
+        (size.x + T::one()) * (size.y + T::one()) * (size.z + T::one())
+    }
+
+    /// The same box moved by an offset.
+    pub fn translate(&self, offset: &Position3<T>) -> Cuboid<T>
+    where
+        T: Add<Output = T>,
+    {
+        Cuboid {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_indexes_voxels() {
+        let mut grid = VoxelGrid::new(Position3 { x: 2, y: 3, z: 4 }, 0);
+        let position = Position3 { x: 1, y: 2, z: 3 };
+        grid.set(&position, 7);
+
+        assert_eq!(grid[&position], 7);
+        assert_eq!(grid.get(&Position3 { x: 2, y: 0, z: 0 }), None);
+        assert_eq!(
+            grid.position_of(grid.index_of(&position).unwrap()),
+            Some(position)
+        );
+        assert_eq!(grid.iter().filter(|(_, value)| **value == 7).count(), 1);
+    }
+
+    #[test]
+    fn it_finds_neighbours() {
+        let grid = VoxelGrid::new(Position3 { x: 3, y: 3, z: 3 }, ());
+
+        let centre = Position3 { x: 1, y: 1, z: 1 };
+        assert_eq!(grid.neighbours(&centre).count(), 6);
+        assert_eq!(grid.all_neighbours(&centre).count(), 26);
+
+        let corner = Position3 { x: 0, y: 0, z: 0 };
+        assert_eq!(grid.neighbours(&corner).count(), 3);
+        assert_eq!(grid.all_neighbours(&corner).count(), 7);
+    }
+
+    #[test]
+    fn it_intersects_boxes() {
+        let a = Cuboid::from_corners(
+            &Position3 { x: 1, y: 0, z: 1 },
+            &Position3 { x: 1, y: 2, z: 1 },
+        );
+        let b = Cuboid::from_corners(
+            &Position3 { x: 0, y: 0, z: 2 },
+            &Position3 { x: 2, y: 0, z: 2 },
+        );
+
+        assert!(!a.intersects(&b));
+        let fallen = a.translate(&Position3 { x: 0, y: 0, z: 1 });
+        assert!(fallen.intersects(&b));
+        assert_eq!(
+            fallen.intersection(&b),
+            Some(Cuboid::from_corners(
+                &Position3 { x: 1, y: 0, z: 2 },
+                &Position3 { x: 1, y: 0, z: 2 }
+            ))
+        );
+        assert_eq!(a.volume(), 3);
+        assert!(a.contains(&Position3 { x: 1, y: 1, z: 1 }));
+        assert!(!a.contains(&Position3 { x: 1, y: 3, z: 1 }));
+    }
+
+    #[test]
+    fn it_builds_boxes_from_either_convention() {
+        let inclusive = Cuboid::from_corners(
+            &Position3 { x: 2, y: 3, z: 0 },
+            &Position3 { x: 0, y: 1, z: 4 },
+        );
+        let exclusive = Cuboid::from_exclusive(
+            &Position3 { x: 0, y: 1, z: 0 },
+            &Position3 { x: 3, y: 4, z: 5 },
+        );
+
+        assert_eq!(Some(inclusive.clone()), exclusive);
+        assert_eq!(inclusive.volume(), 45);
+        assert!(inclusive.contains(&Position3 { x: 2, y: 3, z: 4 }));
+        assert_eq!(
+            Cuboid::from_exclusive(
+                &Position3 { x: 0, y: 0, z: 0 },
+                &Position3 { x: 1, y: 0, z: 1 }
+            ),
+            None
+        );
+    }
+}